@@ -85,13 +85,34 @@ BEGIN
     END IF;
 END $$;
 
+-- Add deleted_at column; deleted posts stay in the trash until restored or purged
+DO $$
+BEGIN
+    IF NOT EXISTS (
+        SELECT 1 
+        FROM information_schema.columns 
+        WHERE table_name = 'posts' AND column_name = 'deleted_at'
+    ) THEN
+        ALTER TABLE posts ADD COLUMN deleted_at TIMESTAMP WITH TIME ZONE;
+    END IF;
+END $$;
+
 -- Create indexes for performance optimization
 CREATE INDEX IF NOT EXISTS idx_users_name ON users (LOWER(name));
-CREATE INDEX IF NOT EXISTS idx_users_email ON users (LOWER(email));
+-- Emails are unique ignoring case; this also serves the case-insensitive
+-- email filters. It replaces the plain idx_users_email of earlier versions.
+DROP INDEX IF EXISTS idx_users_email;
+CREATE UNIQUE INDEX IF NOT EXISTS users_email_lower_key ON users (LOWER(email));
 CREATE INDEX IF NOT EXISTS idx_users_phone ON users (phone);
 CREATE INDEX IF NOT EXISTS idx_posts_title ON posts (LOWER(title));
 CREATE INDEX IF NOT EXISTS idx_posts_content ON posts USING GIN (to_tsvector('english', content));
 CREATE INDEX IF NOT EXISTS idx_posts_created_at ON posts (created_at DESC);
+CREATE INDEX IF NOT EXISTS idx_posts_deleted_at ON posts (deleted_at) WHERE deleted_at IS NOT NULL;
+
+-- Trigram indexes for typo-tolerant (similarTo) name and title search
+CREATE EXTENSION IF NOT EXISTS pg_trgm;
+CREATE INDEX IF NOT EXISTS idx_users_name_trgm ON users USING GIN (name gin_trgm_ops);
+CREATE INDEX IF NOT EXISTS idx_posts_title_trgm ON posts USING GIN (title gin_trgm_ops);
 
 -- Automatically maintain updated_at column on users and posts table
 CREATE OR REPLACE FUNCTION set_updated_at() RETURNS TRIGGER AS $$
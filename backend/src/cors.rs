@@ -6,7 +6,7 @@ pub fn cors_layer() -> CorsLayer {
     // Load allowed origins from env (comma-separated)
     let allowed_origins = env::var("CORS_ALLOWED_ORIGINS").unwrap_or_else(|_| "*".to_string());
 
-    let cors = if allowed_origins == "*" {
+    if allowed_origins == "*" {
         // Development mode — allow all
         CorsLayer::new()
             .allow_origin(Any)
@@ -24,7 +24,5 @@ pub fn cors_layer() -> CorsLayer {
             .allow_methods([Method::GET, Method::POST, Method::OPTIONS])
             .allow_headers([header::CONTENT_TYPE, header::AUTHORIZATION, header::ACCEPT])
             .allow_credentials(true)
-    };
-
-    cors
+    }
 }
@@ -1,3 +1,4 @@
+use crate::utils::connect_options;
 use dotenvy::dotenv;
 use sqlx::{PgPool, postgres::PgPoolOptions};
 use std::env;
@@ -19,7 +20,7 @@ pub async fn init_postgres() -> Result<PgPool, sqlx::Error> {
 
     let pool = PgPoolOptions::new()
         .max_connections(max_connections)
-        .connect(&database_url)
+        .connect_with(connect_options(&database_url)?)
         .await?;
 
     info!(
@@ -1,181 +1,1659 @@
 extern crate proc_macro;
 use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
 use quote::quote;
-use syn::{Data, DeriveInput, Fields, parse_macro_input};
+use syn::{
+    Attribute, Data, DeriveInput, Expr, Field, Fields, GenericArgument, Ident, LitStr,
+    PathArguments, Type, parse_macro_input,
+};
 
-#[cfg(test)]
-pub mod test_utils;
-
-#[proc_macro_derive(FilterBuilder)]
+// Derives `build_where_clause` (and `find_term`, which looks up a term such
+// as a search value through nested `and` / `or` lists) for a GraphQL filter
+// input struct.
+//
+// Struct attributes:
+//   #[filter(table = "posts")]        table used by relation subqueries
+//   #[filter(table_alias = "p")]      qualify every column as `p.<column>`
+//   #[filter(primary_key = "id")]     key referenced by relations (default "id")
+//   #[filter(soft_delete = "deleted_at")]  relation subqueries skip rows where
+//                                          this column is set
+//
+// Field attributes:
+//   #[filter(column = "title")]       SQL column when it differs from the field name
+//   #[filter(skip)]                   field is not part of the WHERE clause
+//   #[filter(has_many(foreign_key = "user_id"))]    some / none / every related rows
+//   #[filter(belongs_to(foreign_key = "user_id"))]  parent row matches a filter
+//
+// Every other field must be `Option<IntFilter>`, `Option<StringFilter>`,
+// `Option<DateTimeFilter>`, or one of the combinators `and: Option<Vec<Self>>`,
+// `or: Option<Vec<Self>>` and `not: Option<Box<Self>>`.
+#[proc_macro_derive(FilterBuilder, attributes(filter))]
 pub fn filter_builder_derive(input: TokenStream) -> TokenStream {
     // Parse input struct
     let input = parse_macro_input!(input as DeriveInput);
+
+    expand_filter_builder(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn expand_filter_builder(input: &DeriveInput) -> syn::Result<TokenStream2> {
     let struct_name = &input.ident;
+    let table_attrs = parse_table_attrs(&input.attrs)?;
 
     // Ensure struct has named fields
     let fields = match &input.data {
         Data::Struct(data_struct) => match &data_struct.fields {
             Fields::Named(fields_named) => &fields_named.named,
-            _ => panic!("FilterBuilder only works on structs with named fields"),
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    struct_name,
+                    "FilterBuilder only works on structs with named fields",
+                ));
+            }
         },
-        _ => panic!("FilterBuilder only works on structs"),
+        Data::Enum(data_enum) => {
+            return Err(syn::Error::new(
+                data_enum.enum_token.span,
+                "FilterBuilder only works on structs",
+            ));
+        }
+        Data::Union(data_union) => {
+            return Err(syn::Error::new(
+                data_union.union_token.span,
+                "FilterBuilder only works on structs",
+            ));
+        }
     };
 
-    // Generate SQL clause logic for each field type
-    let field_conditions = fields.iter().map(|field| {
-        let field_name = &field.ident;
-        let field_name_str = field_name.as_ref().unwrap().to_string();
-        let field_type = &field.ty;
-        let inner_type_str = quote!(#field_type).to_string();
+    // Generate SQL clause logic for each field, reporting every bad field at once
+    let mut field_conditions = Vec::new();
+    let mut errors: Option<syn::Error> = None;
+    for field in fields {
+        match field_condition(field, struct_name, &table_attrs) {
+            Ok(tokens) => field_conditions.push(tokens),
+            Err(e) => match errors.as_mut() {
+                Some(existing) => existing.combine(e),
+                None => errors = Some(e),
+            },
+        }
+    }
+    if let Some(e) = errors {
+        return Err(e);
+    }
 
-        // Integer filters (e.g., equals, gt, lt, gte, lte)
+    // `and` / `or` lists searched by `find_term`; `not` is left out because
+    // terms under it exclude rows rather than describe them
+    let mut combinators = Vec::new();
+    for field in fields {
+        let field_name = field
+            .ident
+            .as_ref()
+            .expect("named fields always have an ident");
+        if (field_name == "and" || field_name == "or") && !parse_field_attrs(&field.attrs)?.skip {
+            combinators.push(field_name);
+        }
+    }
 
-        if inner_type_str.contains("IntFilter") {
+    // Table metadata used to correlate relation subqueries
+    let table_methods = match &table_attrs.table {
+        Some(table) => {
+            let qualifier = table_attrs.alias.clone().unwrap_or_else(|| table.clone());
+            let from = match &table_attrs.alias {
+                Some(alias) => format!("{} {}", table, alias),
+                None => table.clone(),
+            };
+            let primary_key = &table_attrs.primary_key;
+            let live = match &table_attrs.soft_delete {
+                Some(column) => format!(" AND {}.{} IS NULL", qualifier, column),
+                None => String::new(),
+            };
             quote! {
-                if let Some(ref filter) = self.#field_name {
-                    if let Some(value) = filter.equals {
-                        params.push(serde_json::json!(value));
-                        conds.push(format!("{} = ${}", #field_name_str, params.len()));
-                    }
-                    if let Some(value) = filter.gt {
-                        params.push(serde_json::json!(value));
-                        conds.push(format!("{} > ${}", #field_name_str, params.len()));
-                    }
-                    if let Some(value) = filter.lt {
-                        params.push(serde_json::json!(value));
-                        conds.push(format!("{} < ${}", #field_name_str, params.len()));
-                    }
-                    if let Some(value) = filter.gte {
-                        params.push(serde_json::json!(value));
-                        conds.push(format!("{} >= ${}", #field_name_str, params.len()));
-                    }
-                    if let Some(value) = filter.lte {
-                        params.push(serde_json::json!(value));
-                        conds.push(format!("{} <= ${}", #field_name_str, params.len()));
-                    }
+                // FROM item for this filter's table, including its alias
+                #[allow(dead_code)]
+                fn filter_from(&self) -> &'static str {
+                    #from
+                }
+
+                // Name that qualifies this table's columns (alias or table name)
+                #[allow(dead_code)]
+                fn filter_qualifier(&self) -> &'static str {
+                    #qualifier
+                }
+
+                #[allow(dead_code)]
+                fn filter_primary_key(&self) -> &'static str {
+                    #primary_key
+                }
+
+                // Appended to relation links so soft-deleted rows never match
+                #[allow(dead_code)]
+                fn filter_live_condition(&self) -> &'static str {
+                    #live
                 }
             }
         }
-        // String filters (e.g., equals, contains, starts_with, ends_with)
-        else if inner_type_str.contains("StringFilter") {
-            quote! {
-                if let Some(ref filter) = self.#field_name {
-                    if let Some(ref value) = filter.equals {
-                        params.push(serde_json::json!(value));
-                        conds.push(format!("{} = ${}", #field_name_str, params.len()));
-                    }
-                    if let Some(ref value) = filter.contains {
-                        params.push(serde_json::json!(format!("%{}%", value)));
-                        conds.push(format!("{} LIKE ${}", #field_name_str, params.len()));
-                    }
-                    if let Some(ref value) = filter.starts_with {
-                        params.push(serde_json::json!(format!("{}%", value)));
-                        conds.push(format!("{} LIKE ${}", #field_name_str, params.len()));
-                    }
-                    if let Some(ref value) = filter.ends_with {
-                        params.push(serde_json::json!(format!("%{}", value)));
-                        conds.push(format!("{} LIKE ${}", #field_name_str, params.len()));
-                    }
-                    if let Some(ref value) = filter.contains_insensitive {
-                        params.push(serde_json::json!(format!("%{}%", value)));
-                        conds.push(format!("{} ILIKE ${}", #field_name_str, params.len()));
+        None if table_attrs.soft_delete.is_some() => {
+            return Err(syn::Error::new_spanned(
+                struct_name,
+                "soft_delete requires #[filter(table = \"...\")] on the struct",
+            ));
+        }
+        None => quote! {},
+    };
+
+    Ok(quote! {
+        impl #struct_name {
+            #table_methods
+
+            // First term `pick` finds in this filter or, depth first, anywhere
+            // down its `and` / `or` lists
+            #[allow(dead_code)]
+            pub fn find_term<'a>(
+                &'a self,
+                pick: &dyn Fn(&'a Self) -> Option<&'a str>,
+            ) -> Option<&'a str> {
+                if let Some(term) = pick(self) {
+                    return Some(term);
+                }
+                #(
+                    for filter in self.#combinators.iter().flatten() {
+                        if let Some(term) = filter.find_term(pick) {
+                            return Some(term);
+                        }
                     }
+                )*
+                None
+            }
+
+            // Fails with the first limit the filter tree exceeds
+            pub fn build_where_clause(
+                &self,
+                limits: &crate::utils::FilterLimits,
+            ) -> Result<(String, Vec<crate::utils::SqlParam>), crate::utils::FilterLimitError> {
+                let mut budget = crate::utils::FilterBudget::new(limits);
+                let inner = self.build_where_clause_inner(&mut budget, 1)?;
+                if inner.is_empty() {
+                    Ok(("".to_string(), Vec::new()))
+                } else {
+                    // Placeholders are numbered here, once, for the whole tree
+                    let (sql, params) = inner.render(0);
+                    Ok((format!(" WHERE {}", sql), params))
+                }
+            }
+
+            fn build_where_clause_inner(
+                &self,
+                budget: &mut crate::utils::FilterBudget,
+                depth: usize,
+            ) -> Result<crate::utils::Clause, crate::utils::FilterLimitError> {
+                budget.enter(depth)?;
+                let mut conds: Vec<crate::utils::Clause> = Vec::new();
+                #(#field_conditions)*
+
+                budget.add_conditions(conds.len())?;
+                Ok(crate::utils::Clause::join(conds, " AND "))
+            }
+        }
+    })
+}
+
+// What a filter struct field compiles to
+enum FieldKind {
+    Int,
+    String,
+    DateTime,
+    And,
+    Or,
+    Not,
+    Relation(Relation),
+}
+
+fn field_condition(
+    field: &Field,
+    struct_name: &Ident,
+    table_attrs: &TableAttrs,
+) -> syn::Result<TokenStream2> {
+    let field_name = field
+        .ident
+        .as_ref()
+        .expect("named fields always have an ident");
+    let attrs = parse_field_attrs(&field.attrs)?;
+
+    // Fields marked #[filter(skip)] never contribute to the WHERE clause
+    if attrs.skip {
+        return Ok(quote! {});
+    }
+
+    // SQL column for this field: #[filter(column = "...")] or the field name,
+    // qualified with the struct's table alias unless already qualified
+    let column = {
+        let name = attrs.column.unwrap_or_else(|| field_name.to_string());
+        match &table_attrs.alias {
+            Some(alias) if !name.contains('.') => format!("{}.{}", alias, name),
+            _ => name,
+        }
+    };
+
+    let tokens = match classify_field(field, field_name, struct_name, attrs.relation)? {
+        FieldKind::Int => int_filter_conditions(field_name, &column),
+        FieldKind::String => string_filter_conditions(field_name, &column),
+        FieldKind::DateTime => datetime_filter_conditions(field_name, &column),
+        FieldKind::And => nested_and_conditions(field_name),
+        FieldKind::Or => nested_or_conditions(field_name),
+        FieldKind::Not => nested_not_conditions(field_name),
+        FieldKind::Relation(relation) => {
+            if table_attrs.table.is_none() {
+                return Err(syn::Error::new_spanned(
+                    field_name,
+                    "relation fields require #[filter(table = \"...\")] on the struct",
+                ));
+            }
+            match relation {
+                Relation::HasMany { foreign_key } => has_many_conditions(field_name, &foreign_key),
+                Relation::BelongsTo { foreign_key } => {
+                    belongs_to_conditions(field_name, &foreign_key)
+                }
+            }
+        }
+    };
+
+    Ok(tokens)
+}
+
+// Matches the field's declared type against the supported filter shapes.
+// Types are compared by their final path segment, so `IntFilter` and
+// `crate::filters::IntFilter` match while `BigIntFilterWrapper` does not.
+fn classify_field(
+    field: &Field,
+    field_name: &Ident,
+    struct_name: &Ident,
+    relation: Option<Relation>,
+) -> syn::Result<FieldKind> {
+    let Some(inner) = generic_argument(&field.ty, "Option") else {
+        return Err(syn::Error::new_spanned(
+            &field.ty,
+            "FilterBuilder fields must be `Option<_>`; use #[filter(skip)] to ignore this field",
+        ));
+    };
+
+    if let Some(relation) = relation {
+        return Ok(FieldKind::Relation(relation));
+    }
+
+    match field_name.to_string().as_str() {
+        "and" | "or" => {
+            let is_self_list =
+                generic_argument(inner, "Vec").is_some_and(|item| is_self_type(item, struct_name));
+            if !is_self_list {
+                return Err(syn::Error::new_spanned(
+                    &field.ty,
+                    format!("`{}` must be `Option<Vec<{}>>`", field_name, struct_name),
+                ));
+            }
+            return Ok(if field_name == "and" {
+                FieldKind::And
+            } else {
+                FieldKind::Or
+            });
+        }
+        "not" => {
+            let is_boxed_self =
+                generic_argument(inner, "Box").is_some_and(|item| is_self_type(item, struct_name));
+            if !is_boxed_self {
+                return Err(syn::Error::new_spanned(
+                    &field.ty,
+                    format!("`not` must be `Option<Box<{}>>`", struct_name),
+                ));
+            }
+            return Ok(FieldKind::Not);
+        }
+        _ => {}
+    }
+
+    match plain_type_ident(inner)
+        .map(|ident| ident.to_string())
+        .as_deref()
+    {
+        Some("IntFilter") => Ok(FieldKind::Int),
+        Some("StringFilter") => Ok(FieldKind::String),
+        Some("DateTimeFilter") => Ok(FieldKind::DateTime),
+        _ => Err(syn::Error::new_spanned(
+            inner,
+            format!(
+                "unsupported FilterBuilder field type `{}`; expected IntFilter, StringFilter \
+                 or DateTimeFilter, or mark the field with #[filter(skip)]",
+                quote!(#inner).to_string().replace(' ', "")
+            ),
+        )),
+    }
+}
+
+// Returns `T` when `ty` is `Wrapper<T>` (matched on the final path segment)
+fn generic_argument<'a>(ty: &'a Type, wrapper: &str) -> Option<&'a Type> {
+    let Type::Path(type_path) = ty else {
+        return None;
+    };
+    if type_path.qself.is_some() {
+        return None;
+    }
+    let segment = type_path.path.segments.last()?;
+    if segment.ident != wrapper {
+        return None;
+    }
+    let PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return None;
+    };
+    match args.args.first() {
+        Some(GenericArgument::Type(inner)) if args.args.len() == 1 => Some(inner),
+        _ => None,
+    }
+}
+
+// Returns the final identifier of a plain path type without generic arguments
+fn plain_type_ident(ty: &Type) -> Option<&Ident> {
+    let Type::Path(type_path) = ty else {
+        return None;
+    };
+    if type_path.qself.is_some() {
+        return None;
+    }
+    let segment = type_path.path.segments.last()?;
+    match segment.arguments {
+        PathArguments::None => Some(&segment.ident),
+        _ => None,
+    }
+}
+
+fn is_self_type(ty: &Type, struct_name: &Ident) -> bool {
+    plain_type_ident(ty).is_some_and(|ident| ident == "Self" || ident == struct_name)
+}
+
+// Null checks shared by every scalar filter type (is_null: true/false)
+fn null_check(column: &str) -> TokenStream2 {
+    quote! {
+        if let Some(is_null) = filter.is_null {
+            if is_null {
+                conds.push(crate::utils::Clause::raw(&format!("{} IS NULL", #column)));
+            } else {
+                conds.push(crate::utils::Clause::raw(&format!("{} IS NOT NULL", #column)));
+            }
+        }
+    }
+}
+
+// Integer filters (e.g., equals, gt, lt, gte, lte, in, not_in, between)
+fn int_filter_conditions(field_name: &Ident, column: &str) -> TokenStream2 {
+    let null_check = null_check(column);
+    quote! {
+        if let Some(ref filter) = self.#field_name {
+            if let Some(value) = filter.equals {
+                conds.push(crate::utils::compare(#column, "=", value));
+            }
+            if let Some(value) = filter.gt {
+                conds.push(crate::utils::compare(#column, ">", value));
+            }
+            if let Some(value) = filter.lt {
+                conds.push(crate::utils::compare(#column, "<", value));
+            }
+            if let Some(value) = filter.gte {
+                conds.push(crate::utils::compare(#column, ">=", value));
+            }
+            if let Some(value) = filter.lte {
+                conds.push(crate::utils::compare(#column, "<=", value));
+            }
+            if let Some(ref values) = filter.in_list {
+                budget.check_list(values.len())?;
+                conds.push(crate::utils::list_condition(#column, "= ANY", values.clone(), false));
+            }
+            if let Some(ref values) = filter.not_in {
+                budget.check_list(values.len())?;
+                conds.push(crate::utils::list_condition(#column, "<> ALL", values.clone(), false));
+            }
+            if let Some(ref range) = filter.between {
+                let mut cond = crate::utils::compare(#column, "BETWEEN", range.from);
+                cond.push(" AND ").push_bind(range.to);
+                conds.push(cond);
+            }
+            #null_check
+        }
+    }
+}
+
+// String filters (e.g., equals, contains, starts_with, ends_with, in, not_in,
+// search, matches, similar_to). LIKE patterns escape the user's `%`, `_` and
+// `\` so they match literally. With `mode: INSENSITIVE` both sides are wrapped
+// in LOWER() (matching the LOWER(...) indexes) and `matches` uses `~*`.
+fn string_filter_conditions(field_name: &Ident, column: &str) -> TokenStream2 {
+    let null_check = null_check(column);
+    quote! {
+        if let Some(ref filter) = self.#field_name {
+            let insensitive = filter.is_insensitive();
+            if let Some(ref value) = filter.equals {
+                conds.push(crate::utils::compare_case(#column, "=", value.clone(), insensitive));
+            }
+            if let Some(ref value) = filter.contains {
+                let pattern = format!("%{}%", crate::utils::escape_like(value));
+                conds.push(crate::utils::like_condition(#column, "LIKE", pattern, insensitive));
+            }
+            if let Some(ref value) = filter.starts_with {
+                let pattern = format!("{}%", crate::utils::escape_like(value));
+                conds.push(crate::utils::like_condition(#column, "LIKE", pattern, insensitive));
+            }
+            if let Some(ref value) = filter.ends_with {
+                let pattern = format!("%{}", crate::utils::escape_like(value));
+                conds.push(crate::utils::like_condition(#column, "LIKE", pattern, insensitive));
+            }
+            if let Some(ref value) = filter.contains_insensitive {
+                let pattern = format!("%{}%", crate::utils::escape_like(value));
+                conds.push(crate::utils::like_condition(#column, "ILIKE", pattern, false));
+            }
+            if let Some(ref values) = filter.in_list {
+                budget.check_list(values.len())?;
+                conds.push(crate::utils::list_condition(
+                    #column,
+                    "= ANY",
+                    values.clone(),
+                    insensitive,
+                ));
+            }
+            if let Some(ref values) = filter.not_in {
+                budget.check_list(values.len())?;
+                conds.push(crate::utils::list_condition(
+                    #column,
+                    "<> ALL",
+                    values.clone(),
+                    insensitive,
+                ));
+            }
+            if let Some(ref value) = filter.search {
+                conds.push(crate::utils::text_search_condition(#column, value.clone()));
+            }
+            if let Some(ref value) = filter.matches {
+                let operator = if insensitive { "~*" } else { "~" };
+                conds.push(crate::utils::compare(#column, operator, value.clone()));
+            }
+            if let Some(ref value) = filter.matches_insensitive {
+                conds.push(crate::utils::compare(#column, "~*", value.clone()));
+            }
+            if let Some(ref similar) = filter.similar_to {
+                conds.push(crate::utils::similarity_condition(
+                    #column,
+                    similar.value.clone(),
+                    similar
+                        .threshold
+                        .unwrap_or(crate::utils::DEFAULT_SIMILARITY_THRESHOLD),
+                ));
+            }
+            #null_check
+        }
+    }
+}
+
+// Date/time filters (e.g., equals, before, after, between)
+fn datetime_filter_conditions(field_name: &Ident, column: &str) -> TokenStream2 {
+    let null_check = null_check(column);
+    quote! {
+        if let Some(ref filter) = self.#field_name {
+            if let Some(value) = filter.equals {
+                conds.push(crate::utils::compare(#column, "=", value));
+            }
+            if let Some(value) = filter.before {
+                conds.push(crate::utils::compare(#column, "<", value));
+            }
+            if let Some(value) = filter.after {
+                conds.push(crate::utils::compare(#column, ">", value));
+            }
+            if let Some(value) = filter.on_or_before {
+                conds.push(crate::utils::compare(#column, "<=", value));
+            }
+            if let Some(value) = filter.on_or_after {
+                conds.push(crate::utils::compare(#column, ">=", value));
+            }
+            if let Some(ref range) = filter.between {
+                let mut cond = crate::utils::compare(#column, "BETWEEN", range.from);
+                cond.push(" AND ").push_bind(range.to);
+                conds.push(cond);
+            }
+            #null_check
+        }
+    }
+}
+
+// Handle nested AND filters recursively
+fn nested_and_conditions(field_name: &Ident) -> TokenStream2 {
+    quote! {
+        if let Some(ref subfilters) = self.#field_name {
+            budget.check_list(subfilters.len())?;
+            // Each non-empty nested filter becomes a parenthesized group
+            let mut sub_clauses = Vec::new();
+            for f in subfilters {
+                let clause = f.build_where_clause_inner(budget, depth + 1)?;
+                if !clause.is_empty() {
+                    sub_clauses.push(clause.parenthesized());
+                }
+            }
+            // Combine all nested clauses with AND
+            if !sub_clauses.is_empty() {
+                conds.push(crate::utils::Clause::join(sub_clauses, " AND "));
+            }
+        }
+    }
+}
+
+// Handle nested OR filters recursively
+fn nested_or_conditions(field_name: &Ident) -> TokenStream2 {
+    quote! {
+        if let Some(ref subfilters) = self.#field_name {
+            budget.check_list(subfilters.len())?;
+            // Each non-empty nested filter becomes a parenthesized group
+            let mut sub_clauses = Vec::new();
+            for f in subfilters {
+                let clause = f.build_where_clause_inner(budget, depth + 1)?;
+                if !clause.is_empty() {
+                    sub_clauses.push(clause.parenthesized());
+                }
+            }
+            // Combine all nested clauses with OR, grouped so the
+            // surrounding AND cannot bind tighter than the alternatives
+            if !sub_clauses.is_empty() {
+                conds.push(crate::utils::Clause::join(sub_clauses, " OR ").parenthesized());
+            }
+        }
+    }
+}
+
+// Handle a nested NOT filter recursively
+fn nested_not_conditions(field_name: &Ident) -> TokenStream2 {
+    quote! {
+        if let Some(ref subfilter) = self.#field_name {
+            let clause = subfilter.build_where_clause_inner(budget, depth + 1)?;
+            if !clause.is_empty() {
+                let mut cond = crate::utils::Clause::raw("NOT ");
+                cond.push_clause(clause.parenthesized());
+                conds.push(cond);
+            }
+        }
+    }
+}
+
+// One-to-many: filter parents by some / none / every related row
+fn has_many_conditions(field_name: &Ident, foreign_key: &str) -> TokenStream2 {
+    quote! {
+        if let Some(ref relation) = self.#field_name {
+            let parent_key =
+                format!("{}.{}", self.filter_qualifier(), self.filter_primary_key());
+            // At least one related row matches
+            if let Some(ref f) = relation.some {
+                let link = format!(
+                    "{}.{} = {}{}",
+                    f.filter_qualifier(),
+                    #foreign_key,
+                    parent_key,
+                    f.filter_live_condition()
+                );
+                conds.push(crate::utils::exists_subquery(
+                    f.filter_from(),
+                    &link,
+                    f.build_where_clause_inner(budget, depth + 1)?,
+                    false,
+                ));
+            }
+            // No related row matches
+            if let Some(ref f) = relation.none {
+                let link = format!(
+                    "{}.{} = {}{}",
+                    f.filter_qualifier(),
+                    #foreign_key,
+                    parent_key,
+                    f.filter_live_condition()
+                );
+                conds.push(crate::utils::exists_subquery(
+                    f.filter_from(),
+                    &link,
+                    f.build_where_clause_inner(budget, depth + 1)?,
+                    true,
+                ));
+            }
+            // Every related row matches (vacuously true when there are none)
+            if let Some(ref f) = relation.every {
+                let clause = f.build_where_clause_inner(budget, depth + 1)?;
+                if !clause.is_empty() {
+                    let link = format!(
+                        "{}.{} = {}{}",
+                        f.filter_qualifier(),
+                        #foreign_key,
+                        parent_key,
+                        f.filter_live_condition()
+                    );
+                    let mut negated = crate::utils::Clause::raw("NOT COALESCE(");
+                    negated.push_clause(clause.parenthesized()).push(", FALSE)");
+                    conds.push(crate::utils::exists_subquery(
+                        f.filter_from(),
+                        &link,
+                        negated,
+                        true,
+                    ));
                 }
             }
         }
-        // Handle nested AND filters recursively
-        else if field_name_str == "and" {
+    }
+}
+
+// Many-to-one: filter children by their parent row
+fn belongs_to_conditions(field_name: &Ident, foreign_key: &str) -> TokenStream2 {
+    quote! {
+        if let Some(ref f) = self.#field_name {
+            let link = format!(
+                "{}.{} = {}.{}{}",
+                f.filter_qualifier(),
+                f.filter_primary_key(),
+                self.filter_qualifier(),
+                #foreign_key,
+                f.filter_live_condition()
+            );
+            conds.push(crate::utils::exists_subquery(
+                f.filter_from(),
+                &link,
+                f.build_where_clause_inner(budget, depth + 1)?,
+                false,
+            ));
+        }
+    }
+}
+
+// Struct-level #[filter(table = "...", table_alias = "...", primary_key = "...",
+// soft_delete = "...")]
+struct TableAttrs {
+    table: Option<String>,
+    alias: Option<String>,
+    primary_key: String,
+    soft_delete: Option<String>,
+}
+
+// Field-level #[filter(column = "...", skip, has_many(...), belongs_to(...))]
+#[derive(Default)]
+struct FieldAttrs {
+    column: Option<String>,
+    skip: bool,
+    relation: Option<Relation>,
+}
+
+enum Relation {
+    HasMany { foreign_key: String },
+    BelongsTo { foreign_key: String },
+}
+
+fn parse_table_attrs(attrs: &[Attribute]) -> syn::Result<TableAttrs> {
+    let mut table_attrs = TableAttrs {
+        table: None,
+        alias: None,
+        primary_key: "id".to_string(),
+        soft_delete: None,
+    };
+
+    for attr in attrs.iter().filter(|a| a.path().is_ident("filter")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("table") {
+                table_attrs.table = Some(meta.value()?.parse::<LitStr>()?.value());
+                Ok(())
+            } else if meta.path.is_ident("table_alias") {
+                table_attrs.alias = Some(meta.value()?.parse::<LitStr>()?.value());
+                Ok(())
+            } else if meta.path.is_ident("primary_key") {
+                table_attrs.primary_key = meta.value()?.parse::<LitStr>()?.value();
+                Ok(())
+            } else if meta.path.is_ident("soft_delete") {
+                table_attrs.soft_delete = Some(meta.value()?.parse::<LitStr>()?.value());
+                Ok(())
+            } else {
+                Err(meta.error("unsupported filter attribute"))
+            }
+        })?;
+    }
+
+    Ok(table_attrs)
+}
+
+fn parse_field_attrs(attrs: &[Attribute]) -> syn::Result<FieldAttrs> {
+    let mut field_attrs = FieldAttrs::default();
+
+    for attr in attrs.iter().filter(|a| a.path().is_ident("filter")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("column") {
+                field_attrs.column = Some(meta.value()?.parse::<LitStr>()?.value());
+                return Ok(());
+            }
+            if meta.path.is_ident("skip") {
+                field_attrs.skip = true;
+                return Ok(());
+            }
+
+            let has_many = meta.path.is_ident("has_many");
+            if !has_many && !meta.path.is_ident("belongs_to") {
+                return Err(meta.error("unsupported filter attribute"));
+            }
+
+            let mut foreign_key = None;
+            meta.parse_nested_meta(|inner| {
+                if inner.path.is_ident("foreign_key") {
+                    foreign_key = Some(inner.value()?.parse::<LitStr>()?.value());
+                    Ok(())
+                } else {
+                    Err(inner.error("unsupported relation attribute"))
+                }
+            })?;
+            let foreign_key =
+                foreign_key.ok_or_else(|| meta.error("relation requires foreign_key = \"...\""))?;
+
+            field_attrs.relation = Some(if has_many {
+                Relation::HasMany { foreign_key }
+            } else {
+                Relation::BelongsTo { foreign_key }
+            });
+            Ok(())
+        })?;
+    }
+
+    Ok(field_attrs)
+}
+
+// Derives `build_sort_keys` and `build_order_clause` for a GraphQL ORDER BY
+// input struct.
+//
+// Struct attributes:
+//   #[order(default = "created_at DESC")]  ordering used when no entry sets a column,
+//                                          as `<column> [ASC|DESC] [NULLS FIRST|LAST]`
+//   #[order(primary_key = "id")]           tie-breaker appended as `<key> ASC` (default "id")
+//   #[order(row = "Post")]                 also implement `crate::utils::Keyset` for the
+//                                          row type, reading each sortable column from
+//                                          the row field of the same name
+//
+// Field attributes:
+//   #[order(column = "title")]             SQL column when it differs from the field name
+//   #[order(skip)]                         field is not sortable
+//
+// Every other field must be `Option<SortOrder>`. Only the column names declared
+// here ever reach the SQL, so clients cannot inject arbitrary expressions.
+#[proc_macro_derive(OrderBuilder, attributes(order))]
+pub fn order_builder_derive(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    expand_order_builder(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn expand_order_builder(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let struct_name = &input.ident;
+    let order_attrs = parse_order_attrs(&input.attrs)?;
+
+    let fields = match &input.data {
+        Data::Struct(data_struct) => match &data_struct.fields {
+            Fields::Named(fields_named) => &fields_named.named,
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    struct_name,
+                    "OrderBuilder only works on structs with named fields",
+                ));
+            }
+        },
+        Data::Enum(data_enum) => {
+            return Err(syn::Error::new(
+                data_enum.enum_token.span,
+                "OrderBuilder only works on structs",
+            ));
+        }
+        Data::Union(data_union) => {
+            return Err(syn::Error::new(
+                data_union.union_token.span,
+                "OrderBuilder only works on structs",
+            ));
+        }
+    };
+
+    let mut field_terms = Vec::new();
+    let mut columns = Vec::new();
+    let mut errors: Option<syn::Error> = None;
+    for field in fields {
+        match order_field_term(field) {
+            Ok(Some((tokens, column))) => {
+                field_terms.push(tokens);
+                columns.push(column);
+            }
+            Ok(None) => {}
+            Err(e) => match errors.as_mut() {
+                Some(existing) => existing.combine(e),
+                None => errors = Some(e),
+            },
+        }
+    }
+    if let Some(e) = errors {
+        return Err(e);
+    }
+
+    let default_key = match &order_attrs.default {
+        Some(default) => {
+            let column = &default.column;
+            let descending = default.descending;
+            let nulls_first = match default.nulls_first {
+                Some(first) => quote! { Some(#first) },
+                None => quote! { None },
+            };
             quote! {
-                if let Some(ref subfilters) = self.and {
-                    let mut sub_clauses = Vec::new();
-                    for f in subfilters {
-                        // Build clause and parameters from nested filter
-                        let (clause, mut sub_params) = f.build_where_clause_inner();
-                        if !clause.is_empty() {
-                            // Adjust parameter indices based on current params length
-                            let base = params.len();
-                            let adjusted_clause = {
-                                let mut c = clause;
-                                for i in (1..=sub_params.len()).rev() {
-                                    let old = format!("${}", i);
-                                    let new = format!("${}", base + i);
-                                    c = c.replace(&old, &new);
-                                }
-                                c
-                            };
-                            // Add adjusted subclause and merge parameters
-                            sub_clauses.push(format!("({})", adjusted_clause));
-                            params.append(&mut sub_params);
-                        }
-                    }
-                    // Combine all nested clauses with AND
-                    if !sub_clauses.is_empty() {
-                        conds.push(sub_clauses.join(" AND "));
-                    }
+                if keys.is_empty() {
+                    keys.push(crate::utils::SortKey {
+                        column: #column.to_string(),
+                        descending: #descending,
+                        nulls_first: #nulls_first,
+                        nullable: true,
+                    });
                 }
             }
         }
-        // Handle nested OR filters recursively
-        else if field_name_str == "or" {
+        None => quote! {},
+    };
+    let primary_key = &order_attrs.primary_key;
+
+    // Keyset values come from the same column list, so every column a cursor
+    // can be ordered by has one
+    let keyset_impl = match &order_attrs.row {
+        Some(row) => {
+            if !columns.contains(primary_key) {
+                columns.push(primary_key.clone());
+            }
+            let mut arms = Vec::new();
+            let mut accept_arms = Vec::new();
+            for column in &columns {
+                let field = syn::parse_str::<Ident>(column).map_err(|_| {
+                    syn::Error::new_spanned(
+                        row,
+                        format!("row needs column `{}` to be a field name", column),
+                    )
+                })?;
+                arms.push(quote! {
+                    #column => Some(crate::utils::CursorValue::from(
+                        ::std::clone::Clone::clone(&self.#field),
+                    )),
+                });
+                accept_arms.push(quote! {
+                    #column => crate::utils::cursor_accepts(|row: &#row| &row.#field, value),
+                });
+            }
             quote! {
-                if let Some(ref subfilters) = self.or {
-                    let mut sub_clauses = Vec::new();
-                    for f in subfilters {
-                        // Build clause and parameters from nested filter
-                        let (clause, mut sub_params) = f.build_where_clause_inner();
-                        if !clause.is_empty() {
-                            // Adjust parameter numbering for nested OR filters
-                            let base = params.len();
-                            let adjusted_clause = {
-                                let mut c = clause;
-                                for i in (1..=sub_params.len()).rev() {
-                                    let old = format!("${}", i);
-                                    let new = format!("${}", base + i);
-                                    c = c.replace(&old, &new);
-                                }
-                                c
-                            };
-                            // Add adjusted subclause and merge parameters
-                            sub_clauses.push(format!("({})", adjusted_clause));
-                            params.append(&mut sub_params);
+                impl crate::utils::Keyset for #row {
+                    fn sort_value(&self, column: &str) -> Option<crate::utils::CursorValue> {
+                        match column {
+                            #(#arms)*
+                            _ => None,
                         }
                     }
-                    // Combine all nested clauses with OR
-                    if !sub_clauses.is_empty() {
-                        conds.push(sub_clauses.join(" OR "));
+
+                    fn accepts_sort_value(column: &str, value: &crate::utils::CursorValue) -> bool {
+                        match column {
+                            #(#accept_arms)*
+                            _ => false,
+                        }
                     }
                 }
             }
-        } else {
-            quote! {}
         }
-    });
+        None => quote! {},
+    };
+
+    Ok(quote! {
+        #keyset_impl
 
-    let expanded = quote! {
         impl #struct_name {
-            pub fn build_where_clause(&self) -> (String, Vec<serde_json::Value>) {
-                let (inner, params) = self.build_where_clause_inner();
-                if inner.is_empty() {
-                    ("".to_string(), params)
-                } else {
-                    (format!(" WHERE {}", inner), params)
+            // Fields of one entry apply in declaration order, entries in list order.
+            // Always ends with the primary key, so the order is total.
+            pub fn build_sort_keys(entries: &[Self]) -> Vec<crate::utils::SortKey> {
+                let mut keys: Vec<crate::utils::SortKey> = Vec::new();
+                for entry in entries {
+                    #(#field_terms)*
+                }
+                #default_key
+
+                // Tie-breaker so rows with equal sort keys keep a stable order
+                if !keys.iter().any(|key| key.column == #primary_key) {
+                    keys.push(crate::utils::SortKey {
+                        column: #primary_key.to_string(),
+                        descending: false,
+                        nulls_first: None,
+                        nullable: false,
+                    });
+                }
+                for key in keys.iter_mut().filter(|key| key.column == #primary_key) {
+                    key.nullable = false;
                 }
+                keys
             }
 
-            fn build_where_clause_inner(&self) -> (String, Vec<serde_json::Value>) {
-                let mut conds = Vec::new();
-                let mut params = Vec::new();
-                #(#field_conditions)*
+            pub fn build_order_clause(entries: &[Self]) -> String {
+                crate::utils::order_clause(&Self::build_sort_keys(entries))
+            }
+        }
+    })
+}
 
-                let clause = if conds.is_empty() {
-                    "".to_string()
-                } else {
-                    conds.join(" AND ")
-                };
-                (clause, params)
+// The sort key an entry's field adds and its column, or None for skipped fields
+fn order_field_term(field: &Field) -> syn::Result<Option<(TokenStream2, String)>> {
+    let field_name = field
+        .ident
+        .as_ref()
+        .expect("named fields always have an ident");
+    let attrs = parse_order_field_attrs(&field.attrs)?;
+
+    if attrs.skip {
+        return Ok(None);
+    }
+
+    let is_sort_order = generic_argument(&field.ty, "Option")
+        .and_then(plain_type_ident)
+        .is_some_and(|ident| ident == "SortOrder");
+    if !is_sort_order {
+        return Err(syn::Error::new_spanned(
+            &field.ty,
+            "OrderBuilder fields must be `Option<SortOrder>`; use #[order(skip)] to ignore this field",
+        ));
+    }
+
+    let column = attrs.column.unwrap_or_else(|| field_name.to_string());
+    let term = quote! {
+        if let Some(ref sort) = entry.#field_name {
+            keys.push(sort.sort_key(#column));
+        }
+    };
+    Ok(Some((term, column)))
+}
+
+// Struct-level #[order(default = "...", primary_key = "...", row = "...")]
+struct OrderAttrs {
+    default: Option<DefaultOrder>,
+    primary_key: String,
+    row: Option<syn::Path>,
+}
+
+// Parsed `default = "<column> [ASC|DESC] [NULLS FIRST|LAST]"`
+struct DefaultOrder {
+    column: String,
+    descending: bool,
+    nulls_first: Option<bool>,
+}
+
+fn parse_default_order(lit: &LitStr) -> syn::Result<DefaultOrder> {
+    let value = lit.value();
+    let words: Vec<String> = value
+        .split_whitespace()
+        .map(|word| word.to_ascii_uppercase())
+        .collect();
+    let invalid =
+        || syn::Error::new_spanned(lit, "expected `<column> [ASC|DESC] [NULLS FIRST|LAST]`");
+    let Some(column) = value.split_whitespace().next() else {
+        return Err(invalid());
+    };
+
+    let mut rest = &words[1..];
+    let mut descending = false;
+    if let Some(direction) = rest.first().filter(|d| *d == "ASC" || *d == "DESC") {
+        descending = direction == "DESC";
+        rest = &rest[1..];
+    }
+    let nulls_first = match rest {
+        [] => None,
+        [nulls, first] if nulls == "NULLS" && first == "FIRST" => Some(true),
+        [nulls, last] if nulls == "NULLS" && last == "LAST" => Some(false),
+        _ => return Err(invalid()),
+    };
+
+    Ok(DefaultOrder {
+        column: column.to_string(),
+        descending,
+        nulls_first,
+    })
+}
+
+// Field-level #[order(column = "...", skip)]
+#[derive(Default)]
+struct OrderFieldAttrs {
+    column: Option<String>,
+    skip: bool,
+}
+
+fn parse_order_attrs(attrs: &[Attribute]) -> syn::Result<OrderAttrs> {
+    let mut order_attrs = OrderAttrs {
+        default: None,
+        primary_key: "id".to_string(),
+        row: None,
+    };
+
+    for attr in attrs.iter().filter(|a| a.path().is_ident("order")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("default") {
+                order_attrs.default = Some(parse_default_order(&meta.value()?.parse::<LitStr>()?)?);
+                Ok(())
+            } else if meta.path.is_ident("primary_key") {
+                order_attrs.primary_key = meta.value()?.parse::<LitStr>()?.value();
+                Ok(())
+            } else if meta.path.is_ident("row") {
+                order_attrs.row = Some(meta.value()?.parse::<LitStr>()?.parse()?);
+                Ok(())
+            } else {
+                Err(meta.error("unsupported order attribute"))
+            }
+        })?;
+    }
+
+    Ok(order_attrs)
+}
+
+fn parse_order_field_attrs(attrs: &[Attribute]) -> syn::Result<OrderFieldAttrs> {
+    let mut field_attrs = OrderFieldAttrs::default();
+
+    for attr in attrs.iter().filter(|a| a.path().is_ident("order")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("column") {
+                field_attrs.column = Some(meta.value()?.parse::<LitStr>()?.value());
+                Ok(())
+            } else if meta.path.is_ident("skip") {
+                field_attrs.skip = true;
+                Ok(())
+            } else {
+                Err(meta.error("unsupported order attribute"))
+            }
+        })?;
+    }
+
+    Ok(field_attrs)
+}
+
+// Derives `validate` for a GraphQL mutation input struct: trims fields in
+// place, then checks each field's rules in order and reports the first one
+// every invalid field fails, as `crate::utils::FieldError`s named like the
+// schema field (`user_id` -> `userId`).
+//
+// Field attributes (on `T` or `Option<T>`; `None` is never checked):
+//   #[validate(trim)]                      strip surrounding whitespace first
+//   #[validate(non_blank)]                 reject empty or whitespace-only strings
+//   #[validate(min_length = 3)]            bounds in characters; any usize
+//   #[validate(max_length = MAX_LEN)]      expression, e.g. a const
+//   #[validate(range(min = 0, max = 150))] inclusive bounds for numbers
+//   #[validate(custom = "path::to::fn")]   `fn(&T) -> Result<(), String>`,
+//                                          the error being the message
+//
+// trim, non_blank and the length rules need a `String` field.
+#[proc_macro_derive(Validate, attributes(validate))]
+pub fn validate_derive(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    expand_validate(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn expand_validate(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let struct_name = &input.ident;
+
+    let fields = match &input.data {
+        Data::Struct(data_struct) => match &data_struct.fields {
+            Fields::Named(fields_named) => &fields_named.named,
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    struct_name,
+                    "Validate only works on structs with named fields",
+                ));
+            }
+        },
+        Data::Enum(data_enum) => {
+            return Err(syn::Error::new(
+                data_enum.enum_token.span,
+                "Validate only works on structs",
+            ));
+        }
+        Data::Union(data_union) => {
+            return Err(syn::Error::new(
+                data_union.union_token.span,
+                "Validate only works on structs",
+            ));
+        }
+    };
+
+    let mut field_checks = Vec::new();
+    let mut errors: Option<syn::Error> = None;
+    for field in fields {
+        match field_validation(field) {
+            Ok(tokens) => field_checks.push(tokens),
+            Err(e) => match errors.as_mut() {
+                Some(existing) => existing.combine(e),
+                None => errors = Some(e),
+            },
+        }
+    }
+    if let Some(e) = errors {
+        return Err(e);
+    }
+
+    Ok(quote! {
+        impl #struct_name {
+            pub fn validate(&mut self) -> Result<(), Vec<crate::utils::FieldError>> {
+                let mut errors: Vec<crate::utils::FieldError> = Vec::new();
+                #(#field_checks)*
+                if errors.is_empty() { Ok(()) } else { Err(errors) }
+            }
+        }
+    })
+}
+
+fn field_validation(field: &Field) -> syn::Result<TokenStream2> {
+    let field_name = field
+        .ident
+        .as_ref()
+        .expect("named fields always have an ident");
+    let attrs = parse_validate_field_attrs(&field.attrs)?;
+    if attrs.is_empty() {
+        return Ok(quote! {});
+    }
+
+    let optional = generic_argument(&field.ty, "Option");
+    let value_ty = optional.unwrap_or(&field.ty);
+    let is_string = plain_type_ident(value_ty).is_some_and(|ident| ident == "String");
+    if attrs.needs_string() && !is_string {
+        return Err(syn::Error::new_spanned(
+            &field.ty,
+            "trim, non_blank, min_length and max_length need a `String` or `Option<String>` field",
+        ));
+    }
+
+    let schema_name = camel_case(&field_name.to_string());
+    let label = field_label(&field_name.to_string());
+
+    let trim = if attrs.trim {
+        quote! {
+            if value.trim().len() != value.len() {
+                *value = value.trim().to_string();
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    let mut rules = Vec::new();
+    if attrs.non_blank {
+        let message = format!("{} cannot be empty", label);
+        rules.push(quote! {
+            if value.trim().is_empty() {
+                break 'rules Some(#message.to_string());
+            }
+        });
+    }
+    if let Some(min) = &attrs.min_length {
+        rules.push(quote! {
+            if value.chars().count() < #min {
+                break 'rules Some(format!("{} must be at least {} characters", #label, #min));
+            }
+        });
+    }
+    if let Some(max) = &attrs.max_length {
+        rules.push(quote! {
+            if value.chars().count() > #max {
+                break 'rules Some(format!("{} cannot be longer than {} characters", #label, #max));
+            }
+        });
+    }
+    match (&attrs.range_min, &attrs.range_max) {
+        (Some(min), Some(max)) => rules.push(quote! {
+            if *value < #min || *value > #max {
+                break 'rules Some(format!("{} must be between {} and {}", #label, #min, #max));
+            }
+        }),
+        (Some(min), None) => rules.push(quote! {
+            if *value < #min {
+                break 'rules Some(format!("{} must be at least {}", #label, #min));
+            }
+        }),
+        (None, Some(max)) => rules.push(quote! {
+            if *value > #max {
+                break 'rules Some(format!("{} must be at most {}", #label, #max));
+            }
+        }),
+        (None, None) => {}
+    }
+    for custom in &attrs.custom {
+        rules.push(quote! {
+            if let Err(message) = #custom(&*value) {
+                break 'rules Some(message);
             }
+        });
+    }
+
+    let checks = quote! {
+        #trim
+        let failure: Option<String> = 'rules: {
+            #(#rules)*
+            None
+        };
+        if let Some(message) = failure {
+            errors.push(crate::utils::FieldError::new(#schema_name, message));
         }
     };
+    Ok(match optional {
+        Some(_) => quote! {
+            if let Some(value) = self.#field_name.as_mut() {
+                #checks
+            }
+        },
+        None => quote! {
+            {
+                let value = &mut self.#field_name;
+                #checks
+            }
+        },
+    })
+}
+
+// Field-level #[validate(...)] rules
+#[derive(Default)]
+struct ValidateFieldAttrs {
+    trim: bool,
+    non_blank: bool,
+    min_length: Option<Expr>,
+    max_length: Option<Expr>,
+    range_min: Option<Expr>,
+    range_max: Option<Expr>,
+    custom: Vec<syn::Path>,
+}
+
+impl ValidateFieldAttrs {
+    fn is_empty(&self) -> bool {
+        !self.trim
+            && !self.non_blank
+            && self.min_length.is_none()
+            && self.max_length.is_none()
+            && self.range_min.is_none()
+            && self.range_max.is_none()
+            && self.custom.is_empty()
+    }
+
+    fn needs_string(&self) -> bool {
+        self.trim || self.non_blank || self.min_length.is_some() || self.max_length.is_some()
+    }
+}
+
+fn parse_validate_field_attrs(attrs: &[Attribute]) -> syn::Result<ValidateFieldAttrs> {
+    let mut field_attrs = ValidateFieldAttrs::default();
+
+    for attr in attrs.iter().filter(|a| a.path().is_ident("validate")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("trim") {
+                field_attrs.trim = true;
+                Ok(())
+            } else if meta.path.is_ident("non_blank") {
+                field_attrs.non_blank = true;
+                Ok(())
+            } else if meta.path.is_ident("min_length") {
+                field_attrs.min_length = Some(meta.value()?.parse()?);
+                Ok(())
+            } else if meta.path.is_ident("max_length") {
+                field_attrs.max_length = Some(meta.value()?.parse()?);
+                Ok(())
+            } else if meta.path.is_ident("range") {
+                meta.parse_nested_meta(|bound| {
+                    if bound.path.is_ident("min") {
+                        field_attrs.range_min = Some(bound.value()?.parse()?);
+                        Ok(())
+                    } else if bound.path.is_ident("max") {
+                        field_attrs.range_max = Some(bound.value()?.parse()?);
+                        Ok(())
+                    } else {
+                        Err(bound.error("unsupported range attribute"))
+                    }
+                })
+            } else if meta.path.is_ident("custom") {
+                field_attrs
+                    .custom
+                    .push(meta.value()?.parse::<LitStr>()?.parse()?);
+                Ok(())
+            } else {
+                Err(meta.error("unsupported validate attribute"))
+            }
+        })?;
+    }
+
+    Ok(field_attrs)
+}
+
+// Schema name of a field (`user_id` -> `userId`), as async-graphql renames it
+fn camel_case(name: &str) -> String {
+    let mut parts = name.split('_');
+    let mut camel = parts.next().unwrap_or_default().to_string();
+    for part in parts {
+        let mut chars = part.chars();
+        if let Some(first) = chars.next() {
+            camel.extend(first.to_uppercase());
+            camel.push_str(chars.as_str());
+        }
+    }
+    camel
+}
+
+// Field name as it reads in a message (`user_id` -> `User id`)
+fn field_label(name: &str) -> String {
+    let words = name.replace('_', " ");
+    let mut chars = words.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => words,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syn::parse_quote;
+
+    fn expand_error(input: DeriveInput) -> String {
+        match expand_filter_builder(&input) {
+            Ok(_) => panic!("expected FilterBuilder to reject the input"),
+            Err(e) => e.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_rejects_enums() {
+        // Verifies enums produce a diagnostic instead of a panic
+        let input: DeriveInput = parse_quote! {
+            enum Filters {
+                A,
+            }
+        };
+        assert_eq!(expand_error(input), "FilterBuilder only works on structs");
+    }
+
+    #[test]
+    fn test_rejects_tuple_structs() {
+        // Verifies tuple structs produce a diagnostic instead of a panic
+        let input: DeriveInput = parse_quote! {
+            struct Filters(Option<IntFilter>);
+        };
+        assert_eq!(
+            expand_error(input),
+            "FilterBuilder only works on structs with named fields"
+        );
+    }
+
+    #[test]
+    fn test_rejects_misspelled_filter_type() {
+        // Verifies an unknown filter type is an error rather than silently ignored
+        let input: DeriveInput = parse_quote! {
+            struct Filters {
+                id: Option<IntFiltr>,
+            }
+        };
+        assert!(expand_error(input).contains("unsupported FilterBuilder field type `IntFiltr`"));
+    }
+
+    #[test]
+    fn test_matches_type_paths_exactly() {
+        // Verifies filter types are matched by path segment, not substring
+        let input: DeriveInput = parse_quote! {
+            struct Filters {
+                views: Option<BigIntFilterWrapper>,
+            }
+        };
+        assert!(expand_error(input).contains("`BigIntFilterWrapper`"));
+
+        let input: DeriveInput = parse_quote! {
+            struct Filters {
+                id: Option<crate::filters::IntFilter>,
+                title: Option<StringFilter>,
+                created_at: Option<DateTimeFilter>,
+            }
+        };
+        assert!(expand_filter_builder(&input).is_ok());
+    }
+
+    #[test]
+    fn test_skip_opts_out_of_type_checks() {
+        // Verifies #[filter(skip)] accepts fields of any type
+        let input: DeriveInput = parse_quote! {
+            struct Filters {
+                id: Option<IntFilter>,
+                #[filter(skip)]
+                note: String,
+            }
+        };
+        assert!(expand_filter_builder(&input).is_ok());
+    }
+
+    #[test]
+    fn test_rejects_non_option_fields() {
+        // Verifies non-Option fields are reported
+        let input: DeriveInput = parse_quote! {
+            struct Filters {
+                id: IntFilter,
+            }
+        };
+        assert!(expand_error(input).contains("must be `Option<_>`"));
+    }
+
+    #[test]
+    fn test_rejects_malformed_combinators() {
+        // Verifies and/or/not must recurse into the same filter type
+        let input: DeriveInput = parse_quote! {
+            struct Filters {
+                or: Option<Vec<OtherFilters>>,
+            }
+        };
+        assert_eq!(expand_error(input), "`or` must be `Option<Vec<Filters>>`");
+
+        let input: DeriveInput = parse_quote! {
+            struct Filters {
+                not: Option<Filters>,
+            }
+        };
+        assert_eq!(expand_error(input), "`not` must be `Option<Box<Filters>>`");
+    }
+
+    #[test]
+    fn test_reports_every_bad_field() {
+        // Verifies errors from several fields are combined into one diagnostic set
+        let input: DeriveInput = parse_quote! {
+            struct Filters {
+                a: Option<Foo>,
+                b: Option<Bar>,
+            }
+        };
+        let errors = match expand_filter_builder(&input) {
+            Ok(_) => panic!("expected FilterBuilder to reject the input"),
+            Err(e) => e,
+        };
+        assert_eq!(errors.into_iter().count(), 2);
+    }
+
+    #[test]
+    fn test_rejects_unknown_attributes() {
+        // Verifies attribute typos are reported
+        let input: DeriveInput = parse_quote! {
+            #[filter(tabel = "users")]
+            struct Filters {
+                id: Option<IntFilter>,
+            }
+        };
+        assert_eq!(expand_error(input), "unsupported filter attribute");
+
+        let input: DeriveInput = parse_quote! {
+            struct Filters {
+                #[filter(has_many(foreign = "user_id"))]
+                posts: Option<PostListFilter>,
+            }
+        };
+        assert_eq!(expand_error(input), "unsupported relation attribute");
+    }
+
+    #[test]
+    fn test_relation_requires_table() {
+        // Verifies relation fields need the struct-level table attribute
+        let input: DeriveInput = parse_quote! {
+            struct Filters {
+                #[filter(belongs_to(foreign_key = "user_id"))]
+                user: Option<Box<UserFilters>>,
+            }
+        };
+        assert!(expand_error(input).contains("require #[filter(table"));
+    }
+
+    #[test]
+    fn test_soft_delete_requires_table() {
+        // Verifies soft_delete has a table to qualify its column with
+        let input: DeriveInput = parse_quote! {
+            #[filter(soft_delete = "deleted_at")]
+            struct Filters {
+                id: Option<IntFilter>,
+            }
+        };
+        assert!(expand_error(input).contains("soft_delete requires #[filter(table"));
+    }
+
+    #[test]
+    fn test_order_builder_rejects_non_sort_order_fields() {
+        // Verifies ORDER BY inputs only accept Option<SortOrder> fields
+        let input: DeriveInput = parse_quote! {
+            struct OrderBy {
+                id: Option<SortOrder>,
+                name: Option<String>,
+            }
+        };
+        let error = match expand_order_builder(&input) {
+            Ok(_) => panic!("expected OrderBuilder to reject the input"),
+            Err(e) => e.to_string(),
+        };
+        assert!(error.contains("must be `Option<SortOrder>`"));
+    }
+
+    #[test]
+    fn test_order_builder_rejects_unknown_attributes() {
+        // Verifies attribute typos on ORDER BY inputs are reported
+        let input: DeriveInput = parse_quote! {
+            #[order(defualt = "id DESC")]
+            struct OrderBy {
+                id: Option<SortOrder>,
+            }
+        };
+        let error = match expand_order_builder(&input) {
+            Ok(_) => panic!("expected OrderBuilder to reject the input"),
+            Err(e) => e.to_string(),
+        };
+        assert_eq!(error, "unsupported order attribute");
+    }
+
+    #[test]
+    fn test_order_builder_row_needs_field_columns() {
+        // Verifies a keyset row type is only derived for columns that name row fields
+        let input: DeriveInput = parse_quote! {
+            #[order(row = "Post")]
+            struct OrderBy {
+                #[order(column = "LOWER(title)")]
+                title: Option<SortOrder>,
+            }
+        };
+        let error = match expand_order_builder(&input) {
+            Ok(_) => panic!("expected OrderBuilder to reject the input"),
+            Err(e) => e.to_string(),
+        };
+        assert!(error.contains("row needs column `LOWER(title)`"));
+    }
+
+    #[test]
+    fn test_order_builder_rejects_malformed_default() {
+        // Verifies the default ordering is checked at compile time
+        let input: DeriveInput = parse_quote! {
+            #[order(default = "created_at DOWN")]
+            struct OrderBy {
+                id: Option<SortOrder>,
+            }
+        };
+        let error = match expand_order_builder(&input) {
+            Ok(_) => panic!("expected OrderBuilder to reject the input"),
+            Err(e) => e.to_string(),
+        };
+        assert!(error.contains("[ASC|DESC] [NULLS FIRST|LAST]"));
+
+        let input: DeriveInput = parse_quote! {
+            #[order(default = "created_at desc nulls last")]
+            struct OrderBy {
+                id: Option<SortOrder>,
+            }
+        };
+        assert!(expand_order_builder(&input).is_ok());
+    }
+
+    fn validate_error(input: DeriveInput) -> String {
+        match expand_validate(&input) {
+            Ok(_) => panic!("expected Validate to reject the input"),
+            Err(e) => e.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_validate_rejects_string_rules_on_other_types() {
+        // Verifies trim / length rules are only accepted on String fields
+        let input: DeriveInput = parse_quote! {
+            struct Input {
+                #[validate(max_length = 10)]
+                age: Option<i32>,
+            }
+        };
+        assert!(validate_error(input).contains("need a `String` or `Option<String>` field"));
+
+        let input: DeriveInput = parse_quote! {
+            struct Input {
+                #[validate(trim, non_blank, max_length = MAX_LEN)]
+                title: Option<String>,
+                #[validate(range(min = 0, max = 150), custom = "check_age")]
+                age: i32,
+                untouched: Vec<u8>,
+            }
+        };
+        assert!(expand_validate(&input).is_ok());
+    }
+
+    #[test]
+    fn test_validate_rejects_unknown_attributes() {
+        // Verifies rule typos are reported
+        let input: DeriveInput = parse_quote! {
+            struct Input {
+                #[validate(non_empty)]
+                title: String,
+            }
+        };
+        assert_eq!(validate_error(input), "unsupported validate attribute");
+
+        let input: DeriveInput = parse_quote! {
+            struct Input {
+                #[validate(range(minimum = 0))]
+                age: i32,
+            }
+        };
+        assert_eq!(validate_error(input), "unsupported range attribute");
+    }
+
+    #[test]
+    fn test_validate_rejects_enums() {
+        // Verifies enums produce a diagnostic instead of a panic
+        let input: DeriveInput = parse_quote! {
+            enum Input {
+                A,
+            }
+        };
+        assert_eq!(validate_error(input), "Validate only works on structs");
+    }
 
-    TokenStream::from(expanded)
+    #[test]
+    fn test_field_names_and_labels() {
+        // Verifies errors name schema fields and messages read naturally
+        assert_eq!(camel_case("user_id"), "userId");
+        assert_eq!(camel_case("title"), "title");
+        assert_eq!(field_label("user_id"), "User id");
+        assert_eq!(field_label("title"), "Title");
+    }
 }
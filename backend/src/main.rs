@@ -1,4 +1,4 @@
-use async_graphql::{EmptySubscription, Schema, http::GraphiQLSource};
+use async_graphql::{EmptySubscription, Schema, dataloader::DataLoader, http::GraphiQLSource};
 use async_graphql_axum::GraphQL;
 mod cors;
 mod db;
@@ -7,26 +7,59 @@ mod utils;
 
 use cors::cors_layer;
 use db::init_postgres;
-use resolvers::{Mutation, Query};
+use resolvers::{
+    Mutation, PostLoader, PostsCountLoader, Query, UserLoader, UserPostsLoader, purge_expired_posts,
+};
+use utils::{FilterLimits, TrashRetention};
 
 use axum::{
     Router,
     response::{self, IntoResponse},
     routing::{get, post_service},
 };
+use sqlx::PgPool;
+use std::time::Duration;
 use tokio::net::TcpListener;
+use tracing::{error, info};
 
 async fn graphiql() -> impl IntoResponse {
     response::Html(GraphiQLSource::build().endpoint("/graphql").finish())
 }
 
+// Purges expired posts from the trash at startup and then hourly
+fn spawn_trash_purge(pool: PgPool, retention: TrashRetention) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(60 * 60));
+        loop {
+            interval.tick().await;
+            match purge_expired_posts(&pool, retention).await {
+                Ok(0) => {}
+                Ok(purged) => info!("Purged {} posts from the trash", purged),
+                Err(e) => error!("Failed to purge trashed posts: {}", e),
+            }
+        }
+    });
+}
+
 #[tokio::main]
 async fn main() {
     let pool = init_postgres()
         .await
         .expect("Failed to initialize database connection");
-    let schema = Schema::build(Query::default(), Mutation::default(), EmptySubscription)
+    spawn_trash_purge(pool.clone(), TrashRetention::from_env());
+    let schema = Schema::build(Query, Mutation, EmptySubscription)
+        .data(DataLoader::new(UserLoader::new(pool.clone()), tokio::spawn))
+        .data(DataLoader::new(PostLoader::new(pool.clone()), tokio::spawn))
+        .data(DataLoader::new(
+            UserPostsLoader::new(pool.clone()),
+            tokio::spawn,
+        ))
+        .data(DataLoader::new(
+            PostsCountLoader::new(pool.clone()),
+            tokio::spawn,
+        ))
         .data(pool)
+        .data(FilterLimits::from_env())
         .finish();
 
     let app = Router::new()
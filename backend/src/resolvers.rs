@@ -1,12 +1,25 @@
-use crate::utils::bind_dynamic_params;
-use async_graphql::{Context, InputObject, Object, Result};
-use backend::FilterBuilder;
+use crate::utils::{
+    Clause, CursorValue, FieldError, FilterLimitError, FilterLimits, GlobalId, Keyset,
+    KeysetCursor, SortKey, SqlParam, TrashRetention, bind_dynamic_params, keyset_condition,
+    order_clause,
+};
+use async_graphql::connection::{self, Connection, Edge, OpaqueCursor};
+use async_graphql::dataloader::{DataLoader, Loader};
+use async_graphql::{
+    Context, Enum, ErrorExtensions, ID, InputObject, Interface, Object, OutputType, Result,
+    SimpleObject, value,
+};
+use backend::{FilterBuilder, OrderBuilder, Validate};
+use sqlx::postgres::{PgDatabaseError, PgRow};
 use sqlx::types::chrono::{DateTime, Utc};
 use sqlx::{FromRow, PgPool, Postgres, QueryBuilder};
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
 
 //  Models
-#[derive(FromRow)]
-struct User {
+#[derive(FromRow, Clone)]
+pub struct User {
     id: i32,
     name: Option<String>,
     age: Option<i32>,
@@ -14,29 +27,53 @@ struct User {
     phone: Option<String>,
     created_at: Option<DateTime<Utc>>,
     updated_at: Option<DateTime<Utc>>,
+    // Only selected when the users query runs a similarity search on name
+    #[sqlx(default)]
+    similarity: Option<f32>,
 }
 
-#[derive(FromRow)]
-struct Post {
+#[derive(FromRow, Clone)]
+pub struct Post {
     id: i32,
     user_id: Option<i32>,
     title: Option<String>,
     created_at: Option<DateTime<Utc>>,
     updated_at: Option<DateTime<Utc>>,
     content: Option<String>,
+    deleted_at: Option<DateTime<Utc>>,
+    // Only selected when the posts query runs a full-text search on content
+    #[sqlx(default)]
+    rank: Option<f32>,
+    #[sqlx(default)]
+    headline: Option<String>,
+    // Only selected when the posts query runs a similarity search on title
+    #[sqlx(default)]
+    similarity: Option<f32>,
 }
 
 //  Input Filters
-#[derive(InputObject)]
+#[derive(InputObject, Default, Debug)]
 struct IntFilter {
     equals: Option<i32>,
     gt: Option<i32>,
     lt: Option<i32>,
     gte: Option<i32>,
     lte: Option<i32>,
+    #[graphql(name = "in")]
+    in_list: Option<Vec<i32>>,
+    not_in: Option<Vec<i32>>,
+    between: Option<IntRange>,
+    is_null: Option<bool>,
 }
 
-#[derive(InputObject)]
+// Inclusive range used by IntFilter's `between`
+#[derive(InputObject, Debug)]
+struct IntRange {
+    from: i32,
+    to: i32,
+}
+
+#[derive(InputObject, Default, Debug)]
 struct StringFilter {
     equals: Option<String>,
     contains: Option<String>,
@@ -44,47 +81,410 @@ struct StringFilter {
     ends_with: Option<String>,
     #[graphql(name = "containsInsensitive")]
     contains_insensitive: Option<String>,
+    #[graphql(name = "in")]
+    in_list: Option<Vec<String>>,
+    not_in: Option<Vec<String>>,
+    // Full-text search (websearch syntax: words, "phrases", -exclusions, OR)
+    search: Option<String>,
+    // POSIX regular expressions (`~` / `~*`)
+    matches: Option<String>,
+    matches_insensitive: Option<String>,
+    is_null: Option<bool>,
+    // Typo-tolerant trigram match (pg_trgm)
+    similar_to: Option<SimilarityInput>,
+    // Case sensitivity of equals, contains, startsWith, endsWith, in, notIn and matches
+    mode: Option<StringMode>,
+}
+
+// Rows at least `threshold` similar (0 to 1, 0.3 by default), prefiltered with the
+// indexed `%` operator
+#[derive(InputObject, Debug)]
+struct SimilarityInput {
+    value: String,
+    #[graphql(validator(minimum = 0.0, maximum = 1.0))]
+    threshold: Option<f64>,
+}
+
+#[derive(Enum, Copy, Clone, Eq, PartialEq, Debug)]
+enum StringMode {
+    Default,
+    Insensitive,
+}
+
+impl StringFilter {
+    fn is_insensitive(&self) -> bool {
+        self.mode == Some(StringMode::Insensitive)
+    }
+}
+
+#[derive(InputObject, Default, Debug)]
+struct DateTimeFilter {
+    equals: Option<DateTime<Utc>>,
+    before: Option<DateTime<Utc>>,
+    after: Option<DateTime<Utc>>,
+    on_or_before: Option<DateTime<Utc>>,
+    on_or_after: Option<DateTime<Utc>>,
+    between: Option<DateTimeRange>,
+    is_null: Option<bool>,
+}
+
+// Inclusive range used by DateTimeFilter.between
+#[derive(InputObject, Debug)]
+struct DateTimeRange {
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
 }
 
-#[derive(InputObject, FilterBuilder)]
+#[derive(InputObject, FilterBuilder, Default, Debug)]
+#[filter(table = "users")]
 struct UserFilters {
     id: Option<IntFilter>,
     name: Option<StringFilter>,
     age: Option<IntFilter>,
     email: Option<StringFilter>,
     phone: Option<StringFilter>,
+    created_at: Option<DateTimeFilter>,
+    updated_at: Option<DateTimeFilter>,
+    #[filter(has_many(foreign_key = "user_id"))]
+    posts: Option<PostListRelationFilter>,
     and: Option<Vec<UserFilters>>,
     or: Option<Vec<UserFilters>>,
+    not: Option<Box<UserFilters>>,
 }
 
-#[derive(InputObject, FilterBuilder)]
+#[derive(InputObject, FilterBuilder, Default, Debug)]
+#[filter(table = "posts", soft_delete = "deleted_at")]
 struct PostFilters {
     id: Option<IntFilter>,
     user_id: Option<IntFilter>,
     title: Option<StringFilter>,
     content: Option<StringFilter>,
+    created_at: Option<DateTimeFilter>,
+    updated_at: Option<DateTimeFilter>,
+    deleted_at: Option<DateTimeFilter>,
+    #[filter(belongs_to(foreign_key = "user_id"))]
+    user: Option<Box<UserFilters>>,
+    and: Option<Vec<PostFilters>>,
     or: Option<Vec<PostFilters>>,
+    not: Option<Box<PostFilters>>,
+}
+
+impl UserFilters {
+    // Value used to rank users by name similarity
+    fn name_similarity(&self) -> Option<&str> {
+        self.find_term(&|f| similarity_value(&f.name))
+    }
+}
+
+impl PostFilters {
+    // Full-text query used to rank posts by content relevance
+    fn content_search(&self) -> Option<&str> {
+        self.find_term(&|f| f.content.as_ref().and_then(|c| c.search.as_deref()))
+    }
+
+    // Value used to rank posts by title similarity
+    fn title_similarity(&self) -> Option<&str> {
+        self.find_term(&|f| similarity_value(&f.title))
+    }
+}
+
+fn similarity_value(filter: &Option<StringFilter>) -> Option<&str> {
+    filter
+        .as_ref()
+        .and_then(|f| f.similar_to.as_ref())
+        .map(|s| s.value.as_str())
+}
+
+// Relation filter over a user's posts
+#[derive(InputObject, Default, Debug)]
+struct PostListRelationFilter {
+    some: Option<PostFilters>,
+    none: Option<PostFilters>,
+    every: Option<PostFilters>,
+}
+
+//  Ordering
+#[derive(Enum, Copy, Clone, Eq, PartialEq, Default)]
+enum SortDirection {
+    #[default]
+    Asc,
+    Desc,
+}
+
+#[derive(Enum, Copy, Clone, Eq, PartialEq)]
+enum NullsOrder {
+    First,
+    Last,
+}
+
+// Direction for one column; nulls default to Postgres' placement
+// (last for ASC, first for DESC)
+#[derive(InputObject, Default)]
+struct SortOrder {
+    #[graphql(default)]
+    direction: SortDirection,
+    nulls: Option<NullsOrder>,
+}
+
+impl SortOrder {
+    fn sort_key(&self, column: &str) -> SortKey {
+        SortKey {
+            column: column.to_string(),
+            descending: self.direction == SortDirection::Desc,
+            nulls_first: self.nulls.map(|nulls| nulls == NullsOrder::First),
+            nullable: true,
+        }
+    }
+}
+
+#[derive(InputObject, OrderBuilder, Default)]
+#[order(row = "User")]
+struct UserOrderBy {
+    id: Option<SortOrder>,
+    name: Option<SortOrder>,
+    age: Option<SortOrder>,
+    email: Option<SortOrder>,
+    phone: Option<SortOrder>,
+    created_at: Option<SortOrder>,
+    updated_at: Option<SortOrder>,
+}
+
+#[derive(InputObject, OrderBuilder, Default)]
+#[order(default = "created_at DESC", row = "Post")]
+struct PostOrderBy {
+    id: Option<SortOrder>,
+    user_id: Option<SortOrder>,
+    title: Option<SortOrder>,
+    content: Option<SortOrder>,
+    created_at: Option<SortOrder>,
+    updated_at: Option<SortOrder>,
+    deleted_at: Option<SortOrder>,
 }
 
 //  CRUD Input Types
-#[derive(InputObject)]
+// Mutations call `validate()` first, so inputs reaching SQL are trimmed and
+// within these bounds
+
+// Same limit as MAX_TITLE_LENGTH in the frontend's constants.ts
+const MAX_TITLE_LENGTH: usize = 50;
+// Rows one createPosts call may insert (3 bind parameters each, well under
+// Postgres' 65535 per statement)
+const MAX_BULK_POSTS: usize = 1000;
+// users.name and users.email are VARCHAR(255)
+const MAX_NAME_LENGTH: usize = 255;
+const MAX_EMAIL_LENGTH: usize = 255;
+// Same bounds as the frontend's age filter
+const MIN_AGE: i32 = 0;
+const MAX_AGE: i32 = 150;
+// E.164 allows at most 15 digits; 7 covers local numbers without area code
+const MIN_PHONE_DIGITS: usize = 7;
+const MAX_PHONE_DIGITS: usize = 15;
+
+#[derive(InputObject, Validate)]
 struct CreatePostInput {
     user_id: i32,
+    #[validate(trim, non_blank, max_length = MAX_TITLE_LENGTH)]
     title: String,
+    #[validate(trim)]
     content: Option<String>,
 }
 
-#[derive(InputObject)]
+#[derive(InputObject, Validate)]
 struct UpdatePostInput {
     id: i32,
+    #[validate(trim, non_blank, max_length = MAX_TITLE_LENGTH)]
+    title: Option<String>,
+    #[validate(trim)]
+    content: Option<String>,
+}
+
+// Fields updatePosts sets on every matching post; omitted ones are kept
+// and an empty content clears it
+#[derive(InputObject, Validate)]
+struct PostChangesInput {
+    #[validate(trim, non_blank, max_length = MAX_TITLE_LENGTH)]
     title: Option<String>,
+    #[validate(trim)]
     content: Option<String>,
+    user_id: Option<i32>,
+}
+
+// Posts a bulk mutation created, changed or deleted, or on a dry run would
+// have, in id order
+#[derive(SimpleObject, Default)]
+struct BulkPostsResult {
+    ids: Vec<i32>,
+    count: i32,
+    dry_run: bool,
+}
+
+impl BulkPostsResult {
+    fn new(mut ids: Vec<i32>, dry_run: bool) -> Self {
+        ids.sort_unstable();
+        BulkPostsResult {
+            count: ids.len() as i32,
+            ids,
+            dry_run,
+        }
+    }
+}
+
+#[derive(InputObject, Validate)]
+struct CreateUserInput {
+    #[validate(trim, non_blank, max_length = MAX_NAME_LENGTH)]
+    name: String,
+    #[validate(range(min = MIN_AGE, max = MAX_AGE))]
+    age: i32,
+    #[validate(trim, max_length = MAX_EMAIL_LENGTH, custom = "validate_email")]
+    email: String,
+    #[validate(trim, custom = "validate_phone")]
+    phone: Option<String>,
+}
+
+// Omitted fields are left unchanged; an empty phone clears it
+#[derive(InputObject, Validate)]
+struct UpdateUserInput {
+    id: i32,
+    #[validate(trim, non_blank, max_length = MAX_NAME_LENGTH)]
+    name: Option<String>,
+    #[validate(range(min = MIN_AGE, max = MAX_AGE))]
+    age: Option<i32>,
+    #[validate(trim, max_length = MAX_EMAIL_LENGTH, custom = "validate_email")]
+    email: Option<String>,
+    #[validate(trim, custom = "validate_phone")]
+    phone: Option<String>,
+}
+
+// What deleteUser does with the user's posts
+#[derive(Enum, Copy, Clone, Eq, PartialEq, Default)]
+enum UserPostsOnDelete {
+    // Fail when the user has any posts
+    #[default]
+    Refuse,
+    // Delete the posts with the user
+    Cascade,
+    // Move the posts to `reassignTo` first
+    Reassign,
+}
+
+// Same shape the frontend accepts (EMAIL_REGEX): something@something.tld
+// with no whitespace and a single `@`
+fn validate_email(email: &str) -> Result<(), String> {
+    let valid = !email.contains(char::is_whitespace)
+        && email.split_once('@').is_some_and(|(local, domain)| {
+            !local.is_empty()
+                && !domain.contains('@')
+                && domain
+                    .char_indices()
+                    .any(|(i, c)| c == '.' && i > 0 && i + 1 < domain.len())
+        });
+    if !valid {
+        return Err(format!("Invalid email address: {}", email));
+    }
+    Ok(())
+}
+
+// Digits with an optional leading `+` and spaces, dashes, dots or
+// parentheses as separators; empty means no phone
+fn validate_phone(phone: &str) -> Result<(), String> {
+    if phone.is_empty() {
+        return Ok(());
+    }
+    let digits = phone.chars().filter(char::is_ascii_digit).count();
+    let valid = phone
+        .strip_prefix('+')
+        .unwrap_or(phone)
+        .chars()
+        .all(|c| c.is_ascii_digit() || " -.()".contains(c))
+        && (MIN_PHONE_DIGITS..=MAX_PHONE_DIGITS).contains(&digits);
+    if !valid {
+        return Err(format!("Invalid phone number: {}", phone));
+    }
+    Ok(())
+}
+
+// WHERE clause of updatePosts / deletePosts, limited to live posts. A
+// filter without conditions would touch every post, so it is refused
+// rather than read as "all".
+fn bulk_post_filter(ctx: &Context<'_>, filters: &PostFilters) -> Result<(String, Vec<SqlParam>)> {
+    let (where_clause, params) = filters
+        .build_where_clause(&filter_limits(ctx))
+        .map_err(ApiError::from)?;
+    if where_clause.is_empty() {
+        return Err(
+            ApiError::invalid("filters", "Filters must contain at least one condition").into(),
+        );
+    }
+    Ok((and_where(&where_clause, LIVE_POSTS), params))
+}
+
+// Ids of the posts matching a bulk mutation's filter, for dry runs
+async fn matching_post_ids(
+    pool: &PgPool,
+    (where_clause, params): (String, Vec<SqlParam>),
+) -> Result<Vec<i32>> {
+    let sql = format!("SELECT id FROM posts{}", where_clause);
+    let rows = bind_dynamic_params(sqlx::query_as::<_, (i32,)>(&sql), params)
+        .fetch_all(pool)
+        .await
+        .map_err(ApiError::from)?;
+    Ok(rows.into_iter().map(|(id,)| id).collect())
+}
+
+// Empty (already trimmed) optional text is stored as NULL
+fn none_if_empty(value: Option<String>) -> Option<String> {
+    value.filter(|v| !v.is_empty())
+}
+
+// Names the taken address when an email uniqueness constraint fails
+// (users_email_key, or users_email_lower_key for a match ignoring case)
+fn user_write_error(email: Option<&str>, e: sqlx::Error) -> ApiError {
+    match (ApiError::from(e), email) {
+        (ApiError::Conflict { field, .. }, Some(email)) => ApiError::Conflict {
+            field,
+            message: format!("Email is already in use: {}", email),
+        },
+        (err, _) => err,
+    }
+}
+
+//  Node Interface
+// Any object that can be refetched by its global ID through `node(id:)`.
+// `id` is the opaque global ID Relay and Apollo normalize on; the numeric
+// database key that filters and mutations take is `databaseId`.
+#[derive(Interface)]
+#[graphql(field(name = "id", ty = "ID"))]
+enum Node {
+    User(User),
+    Post(Post),
+}
+
+// Object a global ID points at
+enum NodeKey {
+    User(i32),
+    Post(i32),
+}
+
+impl NodeKey {
+    fn parse(id: &ID) -> Result<Self, ApiError> {
+        match GlobalId::decode(id) {
+            Some(GlobalId { type_name, id }) if type_name == "User" => Ok(NodeKey::User(id)),
+            Some(GlobalId { type_name, id }) if type_name == "Post" => Ok(NodeKey::Post(id)),
+            _ => Err(ApiError::invalid(
+                "id",
+                format!("Invalid node ID: {}", id.as_str()),
+            )),
+        }
+    }
 }
 
 //  User Object
 #[Object]
 impl User {
-    async fn id(&self) -> i32 {
+    async fn id(&self) -> ID {
+        GlobalId::new("User", self.id).encode().into()
+    }
+    async fn database_id(&self) -> i32 {
         self.id
     }
     async fn name(&self) -> &Option<String> {
@@ -105,21 +505,57 @@ impl User {
     async fn updated_at(&self) -> &Option<DateTime<Utc>> {
         &self.updated_at
     }
+    // Name similarity to the `similarTo` value, when the query has one
+    async fn similarity(&self) -> Option<f32> {
+        self.similarity
+    }
 
-    async fn posts(&self, ctx: &Context<'_>) -> Result<Vec<Post>> {
-        let pool = ctx.data::<PgPool>()?;
-        let posts = sqlx::query_as::<_, Post>("SELECT * FROM posts WHERE user_id = $1")
-            .bind(self.id)
-            .fetch_all(pool)
-            .await?;
-        Ok(posts)
+    // A page of this user's posts (default newest first, 10 per page)
+    async fn posts(
+        &self,
+        ctx: &Context<'_>,
+        filters: Option<PostFilters>,
+        order_by: Option<Vec<PostOrderBy>>,
+        limit: Option<i32>,
+        offset: Option<i32>,
+    ) -> Result<PostsConnection> {
+        let limits = filter_limits(ctx);
+        let filter = filters
+            .as_ref()
+            .map(|f| f.build_where_clause(&limits))
+            .transpose()
+            .map_err(ApiError::from)?
+            .unwrap_or(("".to_string(), Vec::new()));
+        let page = UserPostsPage::new(
+            filter,
+            PostOrderBy::build_order_clause(order_by.as_deref().unwrap_or_default()),
+            limit.unwrap_or(10).clamp(1, 200),
+            offset.unwrap_or(0).max(0),
+            ctx.look_ahead().field("totalCount").exists(),
+        );
+
+        let loader = ctx.data::<DataLoader<UserPostsLoader>>()?;
+        let key = UserPostsKey {
+            user_id: self.id,
+            page: Arc::new(page),
+        };
+        Ok(loader.load_one(key).await?.unwrap_or_default())
+    }
+
+    // Number of posts by this user, without fetching them
+    async fn posts_count(&self, ctx: &Context<'_>) -> Result<i32> {
+        let loader = ctx.data::<DataLoader<PostsCountLoader>>()?;
+        Ok(loader.load_one(self.id).await?.unwrap_or(0))
     }
 }
 
 //  Post Object
 #[Object]
 impl Post {
-    async fn id(&self) -> i32 {
+    async fn id(&self) -> ID {
+        GlobalId::new("Post", self.id).encode().into()
+    }
+    async fn database_id(&self) -> i32 {
         self.id
     }
     async fn user_id(&self) -> &Option<i32> {
@@ -137,21 +573,258 @@ impl Post {
     async fn content(&self) -> &Option<String> {
         &self.content
     }
+    // When the post was moved to the trash; null while it's live
+    async fn deleted_at(&self) -> &Option<DateTime<Utc>> {
+        &self.deleted_at
+    }
+    // Relevance to the content search, when the query has one
+    async fn rank(&self) -> Option<f32> {
+        self.rank
+    }
+    // Content excerpt with search matches wrapped in <b> tags
+    async fn headline(&self) -> &Option<String> {
+        &self.headline
+    }
+    // Title similarity to the `similarTo` value, when the query has one
+    async fn similarity(&self) -> Option<f32> {
+        self.similarity
+    }
 
     async fn user(&self, ctx: &Context<'_>) -> Result<Option<User>> {
         if let Some(user_id) = self.user_id {
-            let pool = ctx.data::<PgPool>()?;
-            let user = sqlx::query_as::<_, User>("SELECT * FROM users WHERE id = $1")
-                .bind(user_id)
-                .fetch_optional(pool)
-                .await?;
-            Ok(user)
+            let loader = ctx.data::<DataLoader<UserLoader>>()?;
+            Ok(loader.load_one(user_id).await?)
         } else {
             Ok(None)
         }
     }
 }
 
+//  Loaders
+// Post.user, User.posts, User.postsCount and the single-object lookups
+// resolved for a whole response at once: the loaders collect the ids
+// requested while a response is resolved and fetch them with one
+// `= ANY($n)` query each
+
+// Users by id
+pub struct UserLoader {
+    pool: PgPool,
+}
+
+impl UserLoader {
+    pub fn new(pool: PgPool) -> Self {
+        UserLoader { pool }
+    }
+}
+
+impl Loader<i32> for UserLoader {
+    type Value = User;
+    type Error = ApiError;
+
+    async fn load(&self, ids: &[i32]) -> Result<HashMap<i32, User>, Self::Error> {
+        let users = sqlx::query_as::<_, User>("SELECT * FROM users WHERE id = ANY($1)")
+            .bind(ids)
+            .fetch_all(&self.pool)
+            .await?;
+        Ok(users.into_iter().map(|user| (user.id, user)).collect())
+    }
+}
+
+// Live posts by id; trashed ones load as missing
+pub struct PostLoader {
+    pool: PgPool,
+}
+
+impl PostLoader {
+    pub fn new(pool: PgPool) -> Self {
+        PostLoader { pool }
+    }
+}
+
+impl Loader<i32> for PostLoader {
+    type Value = Post;
+    type Error = ApiError;
+
+    async fn load(&self, ids: &[i32]) -> Result<HashMap<i32, Post>, Self::Error> {
+        let sql = format!("SELECT * FROM posts WHERE id = ANY($1) AND {}", LIVE_POSTS);
+        let posts = sqlx::query_as::<_, Post>(&sql)
+            .bind(ids)
+            .fetch_all(&self.pool)
+            .await?;
+        Ok(posts.into_iter().map(|post| (post.id, post)).collect())
+    }
+}
+
+// Arguments of one User.posts selection, shared by every user it is
+// resolved for
+struct UserPostsPage {
+    where_clause: String,
+    params: Vec<SqlParam>,
+    order_clause: String,
+    limit: i32,
+    offset: i32,
+    with_count: bool,
+    // Equal for equal arguments, so users asking for the same page share a batch
+    signature: String,
+}
+
+impl UserPostsPage {
+    fn new(
+        (where_clause, params): (String, Vec<SqlParam>),
+        order_clause: String,
+        limit: i32,
+        offset: i32,
+        with_count: bool,
+    ) -> Self {
+        let signature = format!(
+            "{}|{:?}|{}|{}|{}|{}",
+            where_clause, params, order_clause, limit, offset, with_count
+        );
+        UserPostsPage {
+            where_clause,
+            params,
+            order_clause,
+            limit,
+            offset,
+            with_count,
+            signature,
+        }
+    }
+
+    // This page of posts (and their count) for each of `user_ids`
+    async fn fetch(
+        &self,
+        pool: &PgPool,
+        user_ids: &[i32],
+    ) -> Result<HashMap<i32, PostsConnection>, sqlx::Error> {
+        let mut params = self.params.clone();
+        params.push(SqlParam::from(user_ids.to_vec()));
+        let scope = format!("user_id = ANY(${}) AND {}", params.len(), LIVE_POSTS);
+        let where_clause = and_where(&self.where_clause, &scope);
+
+        // Number each user's posts in the requested order and keep the window
+        let sql = format!(
+            "SELECT * FROM (\
+             SELECT *, ROW_NUMBER() OVER (PARTITION BY user_id{}) AS position FROM posts{}\
+             ) AS numbered WHERE position > {} AND position <= {} ORDER BY user_id, position",
+            self.order_clause,
+            where_clause,
+            self.offset,
+            self.offset + self.limit
+        );
+        let q = bind_dynamic_params(sqlx::query_as::<_, Post>(&sql), params.clone());
+        let mut by_user: HashMap<i32, PostsConnection> = HashMap::new();
+        for post in q.fetch_all(pool).await? {
+            if let Some(user_id) = post.user_id {
+                by_user.entry(user_id).or_default().data.push(post);
+            }
+        }
+
+        if self.with_count {
+            let sql = format!(
+                "SELECT user_id, COUNT(*)::int FROM posts{} GROUP BY user_id",
+                where_clause
+            );
+            let q = bind_dynamic_params(sqlx::query_as::<_, (i32, i32)>(&sql), params);
+            for (user_id, count) in q.fetch_all(pool).await? {
+                by_user.entry(user_id).or_default().total_count = count;
+            }
+        }
+        Ok(by_user)
+    }
+}
+
+#[derive(Clone)]
+pub struct UserPostsKey {
+    user_id: i32,
+    page: Arc<UserPostsPage>,
+}
+
+impl PartialEq for UserPostsKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.user_id == other.user_id && self.page.signature == other.page.signature
+    }
+}
+
+impl Eq for UserPostsKey {}
+
+impl Hash for UserPostsKey {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.user_id.hash(state);
+        self.page.signature.hash(state);
+    }
+}
+
+// A page of each user's posts; one query per distinct set of arguments
+pub struct UserPostsLoader {
+    pool: PgPool,
+}
+
+impl UserPostsLoader {
+    pub fn new(pool: PgPool) -> Self {
+        UserPostsLoader { pool }
+    }
+}
+
+impl Loader<UserPostsKey> for UserPostsLoader {
+    type Value = PostsConnection;
+    type Error = ApiError;
+
+    async fn load(
+        &self,
+        keys: &[UserPostsKey],
+    ) -> Result<HashMap<UserPostsKey, PostsConnection>, Self::Error> {
+        let mut pages: HashMap<&str, (&Arc<UserPostsPage>, Vec<i32>)> = HashMap::new();
+        for key in keys {
+            pages
+                .entry(&key.page.signature)
+                .or_insert_with(|| (&key.page, Vec::new()))
+                .1
+                .push(key.user_id);
+        }
+
+        let mut loaded = HashMap::new();
+        for (page, user_ids) in pages.into_values() {
+            let mut by_user = page.fetch(&self.pool, &user_ids).await?;
+            for user_id in user_ids {
+                let key = UserPostsKey {
+                    user_id,
+                    page: page.clone(),
+                };
+                loaded.insert(key, by_user.remove(&user_id).unwrap_or_default());
+            }
+        }
+        Ok(loaded)
+    }
+}
+
+// Number of live posts per user id
+pub struct PostsCountLoader {
+    pool: PgPool,
+}
+
+impl PostsCountLoader {
+    pub fn new(pool: PgPool) -> Self {
+        PostsCountLoader { pool }
+    }
+}
+
+impl Loader<i32> for PostsCountLoader {
+    type Value = i32;
+    type Error = ApiError;
+
+    async fn load(&self, user_ids: &[i32]) -> Result<HashMap<i32, i32>, Self::Error> {
+        let counts = sqlx::query_as::<_, (i32, i32)>(
+            "SELECT user_id, COUNT(*)::int FROM posts \
+             WHERE user_id = ANY($1) AND deleted_at IS NULL GROUP BY user_id",
+        )
+        .bind(user_ids)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(counts.into_iter().collect())
+    }
+}
+
 // Connection types for paginated responses
 #[derive(Default)]
 struct UsersConnection {
@@ -159,8 +832,8 @@ struct UsersConnection {
     total_count: i32,
 }
 
-#[derive(Default)]
-struct PostsConnection {
+#[derive(Default, Clone)]
+pub struct PostsConnection {
     data: Vec<Post>,
     total_count: i32,
 }
@@ -187,162 +860,677 @@ impl PostsConnection {
     }
 }
 
-// Helper function to execute count query
-async fn fetch_count(
-    pool: &PgPool,
-    where_clause: &str,
-    params: Vec<serde_json::Value>,
-    table_name: &str,
-) -> Result<i32> {
-    let sql = format!("SELECT COUNT(*)::int FROM {}{}", table_name, where_clause);
+// Relay connections (usersConnection / postsConnection), paged by keyset on
+// the active sort so rows inserted between page loads don't shift pages
+type Cursor = OpaqueCursor<KeysetCursor>;
 
-    let mut query = sqlx::query_scalar::<_, i32>(&sql);
-    for p in params {
-        match p {
-            serde_json::Value::Number(n) => {
-                if let Some(i) = n.as_i64() {
-                    query = query.bind(i as i32);
-                } else if let Some(f) = n.as_f64() {
-                    query = query.bind(f);
-                }
-            }
-            serde_json::Value::String(s) => {
-                query = query.bind(s);
-            }
-            serde_json::Value::Bool(b) => {
-                query = query.bind(b);
-            }
-            _ => {}
+// Additional fields on UserConnection / PostConnection
+#[derive(SimpleObject)]
+struct ConnectionFields {
+    // Rows matching the filters, across all pages
+    total_count: i32,
+}
+
+// first / after / last / before of a connection field
+struct PageArgs {
+    after: Option<String>,
+    before: Option<String>,
+    first: Option<i32>,
+    last: Option<i32>,
+}
+
+// The cursor's values for `keys`, each checked against its column's type so
+// a tampered cursor is rejected here rather than by Postgres
+fn cursor_values<T: Keyset>(
+    cursor: &Cursor,
+    keys: &[SortKey],
+    field: &str,
+) -> Result<Vec<CursorValue>> {
+    let values = cursor
+        .values_for(keys)
+        .ok_or_else(|| ApiError::invalid(field, "Cursor does not match the current orderBy"))?;
+    for (key, value) in keys.iter().zip(&values) {
+        if !T::accepts_sort_value(&key.column, value) {
+            return Err(ApiError::invalid(
+                field,
+                format!("Cursor has an invalid value for {}", key.column),
+            )
+            .into());
         }
     }
-
-    let count = query.fetch_one(pool).await?;
-    Ok(count)
+    Ok(values)
 }
 
-//  Query
-#[derive(Default)]
-pub struct Query;
+// Fetches one page of `table` in `keys` order. Paging backward (`last`)
+// walks the reversed order from `before` and flips the rows back.
+async fn fetch_connection<T>(
+    pool: &PgPool,
+    table: &str,
+    (where_clause, params): (String, Vec<SqlParam>),
+    keys: Vec<SortKey>,
+    page: PageArgs,
+) -> Result<Connection<Cursor, T, ConnectionFields>>
+where
+    T: Keyset + OutputType + for<'r> FromRow<'r, PgRow> + Send + Unpin,
+{
+    let total_count = fetch_count(pool, &where_clause, params.clone(), table).await?;
+
+    connection::query(
+        page.after,
+        page.before,
+        page.first,
+        page.last,
+        |after: Option<Cursor>, before: Option<Cursor>, first, last| async move {
+            let backward = last.is_some() && first.is_none();
+            let limit = first.or(last).unwrap_or(10).min(200);
+            let reversed: Vec<SortKey> = keys.iter().map(SortKey::reversed).collect();
+
+            let mut conds = Vec::new();
+            if let Some(ref cursor) = after {
+                conds.push(keyset_condition(
+                    &keys,
+                    &cursor_values::<T>(cursor, &keys, "after")?,
+                ));
+            }
+            if let Some(ref cursor) = before {
+                conds.push(keyset_condition(
+                    &reversed,
+                    &cursor_values::<T>(cursor, &keys, "before")?,
+                ));
+            }
+            let (keyset_sql, keyset_params) = Clause::join(conds, " AND ").render(params.len());
+            let mut params = params;
+            params.extend(keyset_params);
+            let where_clause = match (where_clause.is_empty(), keyset_sql.is_empty()) {
+                (_, true) => where_clause,
+                (true, false) => format!(" WHERE {}", keyset_sql),
+                (false, false) => format!("{} AND {}", where_clause, keyset_sql),
+            };
 
-#[Object]
-impl Query {
-    async fn users(
-        &self,
-        ctx: &Context<'_>,
-        filters: Option<UserFilters>,
-        limit: Option<i32>,
-        offset: Option<i32>,
-    ) -> Result<UsersConnection> {
-        let pool = ctx.data::<PgPool>()?;
-        let limit = limit.unwrap_or(10).clamp(1, 200);
-        let offset = offset.unwrap_or(0).max(0);
+            // One extra row tells whether another page follows
+            let sql = format!(
+                "SELECT * FROM {}{}{} LIMIT {}",
+                table,
+                where_clause,
+                order_clause(if backward { &reversed } else { &keys }),
+                limit + 1
+            );
+            let q = bind_dynamic_params(sqlx::query_as::<_, T>(&sql), params);
+            let mut rows = q.fetch_all(pool).await.map_err(ApiError::from)?;
+            let has_more = rows.len() > limit;
+            rows.truncate(limit);
+            if backward {
+                rows.reverse();
+            }
 
-        let (where_clause, params) = filters
-            .as_ref()
-            .map(|f| f.build_where_clause())
-            .unwrap_or(("".to_string(), Vec::new()));
+            let (has_previous_page, has_next_page) = if backward {
+                (has_more, before.is_some())
+            } else {
+                (after.is_some(), has_more)
+            };
+            let mut connection = Connection::with_additional_fields(
+                has_previous_page,
+                has_next_page,
+                ConnectionFields { total_count },
+            );
+            for row in rows {
+                let position = keys
+                    .iter()
+                    .map(|key| {
+                        let value = row.sort_value(&key.column).ok_or_else(|| {
+                            ApiError::Internal(format!("No cursor value for {}", key.column))
+                        })?;
+                        Ok((key.column.clone(), value))
+                    })
+                    .collect::<Result<Vec<_>, ApiError>>()?;
+                connection
+                    .edges
+                    .push(Edge::new(OpaqueCursor(KeysetCursor(position)), row));
+            }
+            Ok::<_, async_graphql::Error>(connection)
+        },
+    )
+    .await
+}
 
-        // Fetch count
-        let total_count = fetch_count(pool, &where_clause, params.clone(), "users").await?;
+//  Errors
+// Failures reported to clients, each with a machine-readable `code`
+// extension. Deliberately not `Display`: `?` then goes through the `From`
+// below rather than async-graphql's blanket impl, which drops extensions.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ApiError {
+    NotFound(String),
+    Validation(Vec<FieldError>),
+    Conflict {
+        field: Option<String>,
+        message: String,
+    },
+    ForeignKey {
+        field: Option<String>,
+        message: String,
+    },
+    // Logged server-side; clients only get a generic message
+    Internal(String),
+}
 
-        // Fetch data
-        let sql = format!(
-            "SELECT * FROM users{} ORDER BY id ASC LIMIT {} OFFSET {}",
-            where_clause, limit, offset
-        );
+impl ApiError {
+    pub fn invalid(field: &str, message: impl Into<String>) -> Self {
+        ApiError::Validation(vec![FieldError::new(field, message)])
+    }
 
-        let q = bind_dynamic_params(sqlx::query_as::<_, User>(&sql), params);
-        let users = q.fetch_all(pool).await?;
+    pub fn code(&self) -> &'static str {
+        match self {
+            ApiError::NotFound(_) => "NOT_FOUND",
+            ApiError::Validation(_) => "VALIDATION_FAILED",
+            ApiError::Conflict { .. } => "CONFLICT",
+            ApiError::ForeignKey { .. } => "FOREIGN_KEY",
+            ApiError::Internal(_) => "INTERNAL",
+        }
+    }
 
-        Ok(UsersConnection {
-            data: users,
-            total_count,
+    pub fn message(&self) -> String {
+        match self {
+            ApiError::NotFound(message)
+            | ApiError::Conflict { message, .. }
+            | ApiError::ForeignKey { message, .. } => message.clone(),
+            ApiError::Validation(fields) => fields
+                .iter()
+                .map(|f| f.message.as_str())
+                .collect::<Vec<_>>()
+                .join("; "),
+            ApiError::Internal(_) => "Internal server error".to_string(),
+        }
+    }
+}
+
+impl From<ApiError> for async_graphql::Error {
+    fn from(err: ApiError) -> Self {
+        if let ApiError::Internal(detail) = &err {
+            tracing::error!("{}", detail);
+        }
+        async_graphql::Error::new(err.message()).extend_with(|_, e| {
+            e.set("code", err.code());
+            match &err {
+                ApiError::Validation(fields) => e.set(
+                    "fields",
+                    fields
+                        .iter()
+                        .map(|f| match f.limit {
+                            Some((limit, max)) => value!({
+                                "field": f.field,
+                                "message": f.message,
+                                "limit": limit,
+                                "max": max,
+                            }),
+                            None => value!({ "field": f.field, "message": f.message }),
+                        })
+                        .collect::<Vec<_>>(),
+                ),
+                ApiError::Conflict {
+                    field: Some(field), ..
+                }
+                | ApiError::ForeignKey {
+                    field: Some(field), ..
+                } => e.set("field", field.as_str()),
+                _ => {}
+            }
         })
     }
+}
 
-    async fn posts(
-        &self,
-        ctx: &Context<'_>,
-        filters: Option<PostFilters>,
-        limit: Option<i32>,
-        offset: Option<i32>,
-    ) -> Result<PostsConnection> {
+// Postgres errors classified by SQLSTATE; anything unexpected is internal
+impl From<sqlx::Error> for ApiError {
+    fn from(e: sqlx::Error) -> Self {
+        let sqlx::Error::Database(db) = &e else {
+            return match e {
+                sqlx::Error::RowNotFound => ApiError::NotFound("Not found".to_string()),
+                e => ApiError::Internal(e.to_string()),
+            };
+        };
+        let column = db
+            .try_downcast_ref::<PgDatabaseError>()
+            .and_then(PgDatabaseError::column);
+        let field = db
+            .constraint()
+            .and_then(constraint_field)
+            .or(column.map(column_field))
+            .map(str::to_string);
+        let invalid =
+            |message: &str| ApiError::invalid(field.as_deref().unwrap_or("input"), message);
+        match db.code().as_deref() {
+            Some("23505") => ApiError::Conflict {
+                field,
+                message: "A record with this value already exists".to_string(),
+            },
+            Some("23503") => ApiError::ForeignKey {
+                field,
+                message: "Referenced record does not exist".to_string(),
+            },
+            Some("23502") => invalid("Value is required"),
+            Some("23514") => invalid("Value is not allowed"),
+            Some("22001") => invalid("Value is too long"),
+            Some("22003") => invalid("Number is out of range"),
+            Some("22P02") => invalid("Value has an invalid format"),
+            // Regexes only come from `matches` / `matchesInsensitive`, and
+            // Postgres doesn't say which one, so the argument is named
+            Some("2201B") => ApiError::invalid(
+                "filters",
+                format!(
+                    "Invalid regular expression: {}",
+                    db.message()
+                        .trim_start_matches("invalid regular expression: ")
+                ),
+            ),
+            _ => ApiError::Internal(e.to_string()),
+        }
+    }
+}
+
+// Schema field a named constraint guards
+fn constraint_field(constraint: &str) -> Option<&'static str> {
+    match constraint {
+        "users_email_key" | "users_email_lower_key" => Some("email"),
+        "posts_user_id_fkey" => Some("userId"),
+        _ => None,
+    }
+}
+
+// Schema field of a column Postgres reports; input columns are named like
+// their fields except `user_id`
+fn column_field(column: &str) -> &str {
+    match column {
+        "user_id" => "userId",
+        column => column,
+    }
+}
+
+impl From<FilterLimitError> for ApiError {
+    fn from(err: FilterLimitError) -> Self {
+        ApiError::Validation(vec![err.into()])
+    }
+}
+
+// Limits registered on the schema, or the defaults when none are
+fn filter_limits(ctx: &Context<'_>) -> FilterLimits {
+    ctx.data_opt::<FilterLimits>().copied().unwrap_or_default()
+}
+
+// Posts that haven't been moved to the trash, and those that have
+const LIVE_POSTS: &str = "deleted_at IS NULL";
+const TRASHED_POSTS: &str = "deleted_at IS NOT NULL";
+
+// Adds `condition` to a generated WHERE clause, which may be empty
+fn and_where(where_clause: &str, condition: &str) -> String {
+    if where_clause.is_empty() {
+        format!(" WHERE {}", condition)
+    } else {
+        format!("{} AND {}", where_clause, condition)
+    }
+}
+
+// Deletes posts that have been in the trash longer than `retention`,
+// returning how many. The server runs this periodically.
+#[allow(dead_code)]
+pub async fn purge_expired_posts(
+    pool: &PgPool,
+    retention: TrashRetention,
+) -> Result<u64, sqlx::Error> {
+    let result =
+        sqlx::query("DELETE FROM posts WHERE deleted_at < NOW() - make_interval(days => $1)")
+            .bind(retention.days)
+            .execute(pool)
+            .await?;
+    Ok(result.rows_affected())
+}
+
+// Helper function to execute count query
+async fn fetch_count(
+    pool: &PgPool,
+    where_clause: &str,
+    params: Vec<SqlParam>,
+    table_name: &str,
+) -> Result<i32> {
+    let sql = format!("SELECT COUNT(*)::int FROM {}{}", table_name, where_clause);
+
+    let query = bind_dynamic_params(sqlx::query_as::<_, (i32,)>(&sql), params);
+    let (count,) = query.fetch_one(pool).await.map_err(ApiError::from)?;
+    Ok(count)
+}
+
+// An offset-paged `posts` or `trash` listing; `scope` picks live or
+// trashed posts
+async fn fetch_posts(
+    ctx: &Context<'_>,
+    filters: Option<PostFilters>,
+    order_by: Option<Vec<PostOrderBy>>,
+    limit: Option<i32>,
+    offset: Option<i32>,
+    scope: &str,
+) -> Result<PostsConnection> {
+    let pool = ctx.data::<PgPool>()?;
+    let limit = limit.unwrap_or(10).clamp(1, 200);
+    let offset = offset.unwrap_or(0).max(0);
+
+    let limits = filter_limits(ctx);
+    let (where_clause, params) = filters
+        .as_ref()
+        .map(|f| f.build_where_clause(&limits))
+        .transpose()
+        .map_err(ApiError::from)?
+        .unwrap_or(("".to_string(), Vec::new()));
+    let where_clause = and_where(&where_clause, scope);
+
+    // Fetch count
+    let total_count = fetch_count(pool, &where_clause, params.clone(), "posts").await?;
+
+    let mut params = params;
+    let mut select = vec!["*".to_string()];
+    let mut relevance = Vec::new();
+
+    // Rank and highlight content matches
+    if let Some(search) = filters.as_ref().and_then(|f| f.content_search()) {
+        params.push(SqlParam::from(search));
+        let query = format!("websearch_to_tsquery('english', ${})", params.len());
+        select.push(format!(
+            "COALESCE(ts_rank(to_tsvector('english', content), {q}), 0) AS rank, \
+             ts_headline('english', content, {q}) AS headline",
+            q = query
+        ));
+        relevance.push("rank DESC");
+    }
+
+    // Score title similarity
+    if let Some(value) = filters.as_ref().and_then(|f| f.title_similarity()) {
+        params.push(SqlParam::from(value));
+        select.push(format!(
+            "similarity(title, ${}) AS similarity",
+            params.len()
+        ));
+        relevance.push("similarity DESC");
+    }
+
+    // Most relevant first unless the client asked for a specific order
+    let order_clause = if !relevance.is_empty() && order_by.as_ref().is_none_or(|o| o.is_empty()) {
+        format!(" ORDER BY {}, id ASC", relevance.join(", "))
+    } else {
+        PostOrderBy::build_order_clause(order_by.as_deref().unwrap_or_default())
+    };
+    let select = select.join(", ");
+
+    // Fetch data
+    let sql = format!(
+        "SELECT {} FROM posts{}{} LIMIT {} OFFSET {}",
+        select, where_clause, order_clause, limit, offset
+    );
+
+    let q = bind_dynamic_params(sqlx::query_as::<_, Post>(&sql), params);
+    let posts = q.fetch_all(pool).await.map_err(ApiError::from)?;
+
+    Ok(PostsConnection {
+        data: posts,
+        total_count,
+    })
+}
+
+//  Query
+#[derive(Default)]
+pub struct Query;
+
+#[Object]
+impl Query {
+    async fn users(
+        &self,
+        ctx: &Context<'_>,
+        filters: Option<UserFilters>,
+        order_by: Option<Vec<UserOrderBy>>,
+        limit: Option<i32>,
+        offset: Option<i32>,
+    ) -> Result<UsersConnection> {
         let pool = ctx.data::<PgPool>()?;
         let limit = limit.unwrap_or(10).clamp(1, 200);
         let offset = offset.unwrap_or(0).max(0);
 
+        let limits = filter_limits(ctx);
         let (where_clause, params) = filters
             .as_ref()
-            .map(|f| f.build_where_clause())
+            .map(|f| f.build_where_clause(&limits))
+            .transpose()
+            .map_err(ApiError::from)?
             .unwrap_or(("".to_string(), Vec::new()));
 
         // Fetch count
-        let total_count = fetch_count(pool, &where_clause, params.clone(), "posts").await?;
+        let total_count = fetch_count(pool, &where_clause, params.clone(), "users").await?;
+
+        let mut params = params;
+        let mut select = "*".to_string();
+        let mut order_clause =
+            UserOrderBy::build_order_clause(order_by.as_deref().unwrap_or_default());
+
+        // Score name similarity, closest matches first unless the client
+        // asked for a specific order
+        if let Some(value) = filters.as_ref().and_then(|f| f.name_similarity()) {
+            params.push(SqlParam::from(value));
+            select = format!("*, similarity(name, ${}) AS similarity", params.len());
+            if order_by.as_ref().is_none_or(|o| o.is_empty()) {
+                order_clause = " ORDER BY similarity DESC, id ASC".to_string();
+            }
+        }
 
         // Fetch data
         let sql = format!(
-            "SELECT * FROM posts{} ORDER BY created_at DESC LIMIT {} OFFSET {}",
-            where_clause, limit, offset
+            "SELECT {} FROM users{}{} LIMIT {} OFFSET {}",
+            select, where_clause, order_clause, limit, offset
         );
 
-        let q = bind_dynamic_params(sqlx::query_as::<_, Post>(&sql), params);
-        let posts = q.fetch_all(pool).await?;
+        let q = bind_dynamic_params(sqlx::query_as::<_, User>(&sql), params);
+        let users = q.fetch_all(pool).await.map_err(ApiError::from)?;
 
-        Ok(PostsConnection {
-            data: posts,
+        Ok(UsersConnection {
+            data: users,
             total_count,
         })
     }
+
+    // Live posts; trashed ones are listed by `trash`
+    async fn posts(
+        &self,
+        ctx: &Context<'_>,
+        filters: Option<PostFilters>,
+        order_by: Option<Vec<PostOrderBy>>,
+        limit: Option<i32>,
+        offset: Option<i32>,
+    ) -> Result<PostsConnection> {
+        fetch_posts(ctx, filters, order_by, limit, offset, LIVE_POSTS).await
+    }
+
+    // Posts in the trash (default most recently deleted first) until they
+    // are restored or purged
+    async fn trash(
+        &self,
+        ctx: &Context<'_>,
+        filters: Option<PostFilters>,
+        order_by: Option<Vec<PostOrderBy>>,
+        limit: Option<i32>,
+        offset: Option<i32>,
+    ) -> Result<PostsConnection> {
+        let order_by = order_by.filter(|o| !o.is_empty()).unwrap_or_else(|| {
+            vec![PostOrderBy {
+                deleted_at: Some(SortOrder {
+                    direction: SortDirection::Desc,
+                    nulls: None,
+                }),
+                ..Default::default()
+            }]
+        });
+        fetch_posts(ctx, filters, Some(order_by), limit, offset, TRASHED_POSTS).await
+    }
+
+    // Cursor-paged users in `orderBy` order (default `id ASC`)
+    #[allow(clippy::too_many_arguments)]
+    async fn users_connection(
+        &self,
+        ctx: &Context<'_>,
+        filters: Option<UserFilters>,
+        order_by: Option<Vec<UserOrderBy>>,
+        after: Option<String>,
+        before: Option<String>,
+        first: Option<i32>,
+        last: Option<i32>,
+    ) -> Result<Connection<Cursor, User, ConnectionFields>> {
+        let pool = ctx.data::<PgPool>()?;
+        let limits = filter_limits(ctx);
+        let filter = filters
+            .as_ref()
+            .map(|f| f.build_where_clause(&limits))
+            .transpose()
+            .map_err(ApiError::from)?
+            .unwrap_or(("".to_string(), Vec::new()));
+        let keys = UserOrderBy::build_sort_keys(order_by.as_deref().unwrap_or_default());
+
+        let page = PageArgs {
+            after,
+            before,
+            first,
+            last,
+        };
+        fetch_connection(pool, "users", filter, keys, page).await
+    }
+
+    // Cursor-paged posts in `orderBy` order (default `created_at DESC`).
+    // Search relevance ordering is only applied by the offset-paged `posts`.
+    #[allow(clippy::too_many_arguments)]
+    async fn posts_connection(
+        &self,
+        ctx: &Context<'_>,
+        filters: Option<PostFilters>,
+        order_by: Option<Vec<PostOrderBy>>,
+        after: Option<String>,
+        before: Option<String>,
+        first: Option<i32>,
+        last: Option<i32>,
+    ) -> Result<Connection<Cursor, Post, ConnectionFields>> {
+        let pool = ctx.data::<PgPool>()?;
+        let limits = filter_limits(ctx);
+        let filter = filters
+            .as_ref()
+            .map(|f| f.build_where_clause(&limits))
+            .transpose()
+            .map_err(ApiError::from)?
+            .unwrap_or(("".to_string(), Vec::new()));
+        let filter = (and_where(&filter.0, LIVE_POSTS), filter.1);
+        let keys = PostOrderBy::build_sort_keys(order_by.as_deref().unwrap_or_default());
+
+        let page = PageArgs {
+            after,
+            before,
+            first,
+            last,
+        };
+        fetch_connection(pool, "posts", filter, keys, page).await
+    }
+
+    // One user by database id, or null when it doesn't exist
+    async fn user(&self, ctx: &Context<'_>, id: i32) -> Result<Option<User>> {
+        let loader = ctx.data::<DataLoader<UserLoader>>()?;
+        Ok(loader.load_one(id).await?)
+    }
+
+    // One post by database id, or null when it doesn't exist
+    async fn post(&self, ctx: &Context<'_>, id: i32) -> Result<Option<Post>> {
+        let loader = ctx.data::<DataLoader<PostLoader>>()?;
+        Ok(loader.load_one(id).await?)
+    }
+
+    // Any object by its global `id`, or null when it doesn't exist
+    async fn node(&self, ctx: &Context<'_>, id: ID) -> Result<Option<Node>> {
+        Ok(match NodeKey::parse(&id)? {
+            NodeKey::User(id) => {
+                let loader = ctx.data::<DataLoader<UserLoader>>()?;
+                loader.load_one(id).await?.map(Node::User)
+            }
+            NodeKey::Post(id) => {
+                let loader = ctx.data::<DataLoader<PostLoader>>()?;
+                loader.load_one(id).await?.map(Node::Post)
+            }
+        })
+    }
+
+    // Objects for each global ID, in order, with null for missing or
+    // malformed ones. One query per object type however many IDs are asked for.
+    async fn nodes(&self, ctx: &Context<'_>, ids: Vec<ID>) -> Result<Vec<Option<Node>>> {
+        let keys: Vec<Option<NodeKey>> = ids.iter().map(|id| NodeKey::parse(id).ok()).collect();
+        let mut user_ids = Vec::new();
+        let mut post_ids = Vec::new();
+        for key in keys.iter().flatten() {
+            match key {
+                NodeKey::User(id) => user_ids.push(*id),
+                NodeKey::Post(id) => post_ids.push(*id),
+            }
+        }
+        let users = ctx
+            .data::<DataLoader<UserLoader>>()?
+            .load_many(user_ids)
+            .await?;
+        let posts = ctx
+            .data::<DataLoader<PostLoader>>()?
+            .load_many(post_ids)
+            .await?;
+
+        Ok(keys
+            .into_iter()
+            .map(|key| match key? {
+                NodeKey::User(id) => users.get(&id).cloned().map(Node::User),
+                NodeKey::Post(id) => posts.get(&id).cloned().map(Node::Post),
+            })
+            .collect())
+    }
 }
+
 //  Mutations
 #[derive(Default)]
 pub struct Mutation;
 
 #[Object]
 impl Mutation {
-    async fn create_post(&self, ctx: &Context<'_>, input: CreatePostInput) -> Result<Post> {
+    async fn create_post(&self, ctx: &Context<'_>, mut input: CreatePostInput) -> Result<Post> {
         let pool = ctx.data::<PgPool>()?;
-        let title = input.title.trim();
-
-        if title.is_empty() {
-            return Err(async_graphql::Error::new("Title cannot be empty"));
-        }
+        input.validate().map_err(ApiError::Validation)?;
 
         let mut qb = QueryBuilder::<Postgres>::new(
             "INSERT INTO posts (user_id, title, content, created_at, updated_at) ",
         );
 
-        // Normalize empty content to NULL before binding
-        let normalized_content = match &input.content {
-            Some(c) if !c.trim().is_empty() => Some(c.trim().to_string()),
-            _ => None,
-        };
-
         // Build query
         qb.push("VALUES (")
             .push_bind(input.user_id)
             .push(", ")
-            .push_bind(title)
+            .push_bind(input.title)
             .push(", ")
-            .push_bind(normalized_content)
+            .push_bind(none_if_empty(input.content))
             .push(", NOW(), NOW()) ")
-            .push("RETURNING id, user_id, title, created_at, updated_at, content");
-
-        let post = qb
-            .build_query_as::<Post>()
-            .fetch_one(pool)
-            .await
-            .map_err(|e| async_graphql::Error::new(format!("Failed to create post: {}", e)))?;
+            .push("RETURNING id, user_id, title, created_at, updated_at, content, deleted_at");
+
+        let post =
+            qb.build_query_as::<Post>().fetch_one(pool).await.map_err(
+                |e| match ApiError::from(e) {
+                    ApiError::ForeignKey { field, .. } => ApiError::ForeignKey {
+                        field,
+                        message: format!("User {} does not exist", input.user_id),
+                    },
+                    err => err,
+                },
+            )?;
 
         Ok(post)
     }
 
-    async fn update_post(&self, ctx: &Context<'_>, input: UpdatePostInput) -> Result<Post> {
+    async fn update_post(&self, ctx: &Context<'_>, mut input: UpdatePostInput) -> Result<Post> {
         let pool = ctx.data::<PgPool>()?;
 
         if input.title.is_none() && input.content.is_none() {
-            return Err(async_graphql::Error::new("Nothing to update"));
+            return Err(ApiError::invalid("input", "Nothing to update").into());
         }
+        input.validate().map_err(ApiError::Validation)?;
 
         let mut qb = QueryBuilder::<Postgres>::new("UPDATE posts SET ");
         let mut wrote = false;
@@ -357,11 +1545,7 @@ impl Mutation {
         }
 
         if let Some(content) = &input.content {
-            let normalized_content = if content.trim().is_empty() {
-                None
-            } else {
-                Some(content.trim().to_string())
-            };
+            let normalized_content = none_if_empty(Some(content.clone()));
 
             if wrote {
                 qb.push(", ");
@@ -379,37 +1563,484 @@ impl Mutation {
 
         qb.push(" WHERE id = ")
             .push_bind(input.id)
-            .push(" RETURNING id, user_id, title, created_at, updated_at, content");
+            .push(" AND deleted_at IS NULL")
+            .push(" RETURNING id, user_id, title, created_at, updated_at, content, deleted_at");
 
         let post = qb
             .build_query_as::<Post>()
-            .fetch_one(pool)
+            .fetch_optional(pool)
             .await
-            .map_err(|e| async_graphql::Error::new(format!("Failed to update post: {}", e)))?;
+            .map_err(ApiError::from)?
+            .ok_or_else(|| ApiError::NotFound(format!("Post {} not found", input.id)))?;
 
         Ok(post)
     }
 
+    // Moves the post to the trash; `restorePost` brings it back until it is
+    // purged
     async fn delete_post(&self, ctx: &Context<'_>, id: i32) -> Result<bool> {
         let pool = ctx.data::<PgPool>()?;
 
-        let mut qb = QueryBuilder::<Postgres>::new("DELETE FROM posts WHERE id = ");
-        qb.push_bind(id);
+        let affected =
+            sqlx::query("UPDATE posts SET deleted_at = NOW() WHERE id = $1 AND deleted_at IS NULL")
+                .bind(id)
+                .execute(pool)
+                .await
+                .map_err(ApiError::from)?
+                .rows_affected();
+
+        Ok(affected > 0)
+    }
+
+    // Takes a post back out of the trash
+    async fn restore_post(&self, ctx: &Context<'_>, id: i32) -> Result<Post> {
+        let pool = ctx.data::<PgPool>()?;
+
+        let post = sqlx::query_as::<_, Post>(
+            "UPDATE posts SET deleted_at = NULL WHERE id = $1 AND deleted_at IS NOT NULL \
+             RETURNING id, user_id, title, created_at, updated_at, content, deleted_at",
+        )
+        .bind(id)
+        .fetch_optional(pool)
+        .await
+        .map_err(ApiError::from)?
+        .ok_or_else(|| ApiError::NotFound(format!("Post {} is not in the trash", id)))?;
 
-        let affected = qb
-            .build()
+        Ok(post)
+    }
+
+    // Deletes a trashed post for good. Live posts have to be deleted first.
+    async fn purge_post(&self, ctx: &Context<'_>, id: i32) -> Result<bool> {
+        let pool = ctx.data::<PgPool>()?;
+
+        let affected = sqlx::query("DELETE FROM posts WHERE id = $1 AND deleted_at IS NOT NULL")
+            .bind(id)
             .execute(pool)
             .await
-            .map_err(|e| async_graphql::Error::new(format!("Failed to delete post: {}", e)))?
+            .map_err(ApiError::from)?
             .rows_affected();
 
         Ok(affected > 0)
     }
+
+    // Creates every post in one INSERT, or none when any input is invalid or
+    // names a missing user
+    async fn create_posts(
+        &self,
+        ctx: &Context<'_>,
+        mut inputs: Vec<CreatePostInput>,
+    ) -> Result<BulkPostsResult> {
+        let pool = ctx.data::<PgPool>()?;
+
+        if inputs.len() > MAX_BULK_POSTS {
+            return Err(ApiError::invalid(
+                "inputs",
+                format!("At most {} posts can be created at once", MAX_BULK_POSTS),
+            )
+            .into());
+        }
+        let mut field_errors = Vec::new();
+        for (i, input) in inputs.iter_mut().enumerate() {
+            if let Err(errors) = input.validate() {
+                field_errors.extend(
+                    errors
+                        .into_iter()
+                        .map(|e| FieldError::new(&format!("inputs.{}.{}", i, e.field), e.message)),
+                );
+            }
+        }
+        if !field_errors.is_empty() {
+            return Err(ApiError::Validation(field_errors).into());
+        }
+        if inputs.is_empty() {
+            return Ok(BulkPostsResult::default());
+        }
+
+        let mut tx = pool.begin().await.map_err(ApiError::from)?;
+
+        // Name the first input with a missing author; locking the authors
+        // keeps them from being deleted before the insert commits
+        let user_ids: Vec<i32> = inputs.iter().map(|input| input.user_id).collect();
+        let authors: Vec<(i32,)> =
+            sqlx::query_as("SELECT id FROM users WHERE id = ANY($1) FOR SHARE")
+                .bind(&user_ids)
+                .fetch_all(&mut *tx)
+                .await
+                .map_err(ApiError::from)?;
+        if let Some((i, user_id)) = user_ids
+            .iter()
+            .enumerate()
+            .find(|(_, user_id)| !authors.iter().any(|(id,)| id == *user_id))
+        {
+            return Err(ApiError::ForeignKey {
+                field: Some(format!("inputs.{}.userId", i)),
+                message: format!("User {} does not exist", user_id),
+            }
+            .into());
+        }
+
+        let mut qb = QueryBuilder::<Postgres>::new(
+            "INSERT INTO posts (user_id, title, content, created_at, updated_at) ",
+        );
+        qb.push_values(inputs, |mut row, input| {
+            row.push_bind(input.user_id)
+                .push_bind(input.title)
+                .push_bind(none_if_empty(input.content))
+                .push("NOW()")
+                .push("NOW()");
+        });
+        qb.push(" RETURNING id");
+
+        let ids: Vec<(i32,)> = qb
+            .build_query_as()
+            .fetch_all(&mut *tx)
+            .await
+            .map_err(ApiError::from)?;
+        tx.commit().await.map_err(ApiError::from)?;
+
+        Ok(BulkPostsResult::new(
+            ids.into_iter().map(|(id,)| id).collect(),
+            false,
+        ))
+    }
+
+    // Sets `set`'s fields on every post matching `filters` in one UPDATE.
+    // With `dryRun` only reports the posts that would change.
+    async fn update_posts(
+        &self,
+        ctx: &Context<'_>,
+        filters: PostFilters,
+        mut set: PostChangesInput,
+        #[graphql(default)] dry_run: bool,
+    ) -> Result<BulkPostsResult> {
+        let pool = ctx.data::<PgPool>()?;
+
+        if set.title.is_none() && set.content.is_none() && set.user_id.is_none() {
+            return Err(ApiError::invalid("set", "Nothing to update").into());
+        }
+        set.validate().map_err(ApiError::Validation)?;
+        let (where_clause, mut params) = bulk_post_filter(ctx, &filters)?;
+
+        if dry_run {
+            // Nothing is written, so no foreign key catches an unknown author
+            if let Some(user_id) = set.user_id {
+                let author: Option<(i32,)> = sqlx::query_as("SELECT id FROM users WHERE id = $1")
+                    .bind(user_id)
+                    .fetch_optional(pool)
+                    .await
+                    .map_err(ApiError::from)?;
+                if author.is_none() {
+                    return Err(ApiError::ForeignKey {
+                        field: Some("userId".to_string()),
+                        message: format!("User {} does not exist", user_id),
+                    }
+                    .into());
+                }
+            }
+            let ids = matching_post_ids(pool, (where_clause, params)).await?;
+            return Ok(BulkPostsResult::new(ids, true));
+        }
+
+        // SET values are numbered after the filter's parameters
+        let mut assignments = Vec::new();
+        if let Some(title) = set.title {
+            params.push(SqlParam::from(title));
+            assignments.push(format!("title = ${}", params.len()));
+        }
+        if let Some(content) = set.content {
+            params.push(SqlParam::from(content));
+            assignments.push(format!("content = NULLIF(${}, '')", params.len()));
+        }
+        if let Some(user_id) = set.user_id {
+            params.push(SqlParam::from(user_id));
+            assignments.push(format!("user_id = ${}", params.len()));
+        }
+        assignments.push("updated_at = NOW()".to_string());
+
+        let sql = format!(
+            "UPDATE posts SET {}{} RETURNING id",
+            assignments.join(", "),
+            where_clause
+        );
+        let rows = bind_dynamic_params(sqlx::query_as::<_, (i32,)>(&sql), params)
+            .fetch_all(pool)
+            .await
+            .map_err(|e| match (ApiError::from(e), set.user_id) {
+                (ApiError::ForeignKey { field, .. }, Some(user_id)) => ApiError::ForeignKey {
+                    field,
+                    message: format!("User {} does not exist", user_id),
+                },
+                (err, _) => err,
+            })?;
+
+        Ok(BulkPostsResult::new(
+            rows.into_iter().map(|(id,)| id).collect(),
+            false,
+        ))
+    }
+
+    // Moves every post matching `filters` to the trash in one UPDATE. With
+    // `dryRun` only reports the posts that would be deleted.
+    async fn delete_posts(
+        &self,
+        ctx: &Context<'_>,
+        filters: PostFilters,
+        #[graphql(default)] dry_run: bool,
+    ) -> Result<BulkPostsResult> {
+        let pool = ctx.data::<PgPool>()?;
+        let (where_clause, params) = bulk_post_filter(ctx, &filters)?;
+
+        if dry_run {
+            let ids = matching_post_ids(pool, (where_clause, params)).await?;
+            return Ok(BulkPostsResult::new(ids, true));
+        }
+
+        let sql = format!(
+            "UPDATE posts SET deleted_at = NOW(){} RETURNING id",
+            where_clause
+        );
+        let rows = bind_dynamic_params(sqlx::query_as::<_, (i32,)>(&sql), params)
+            .fetch_all(pool)
+            .await
+            .map_err(ApiError::from)?;
+
+        Ok(BulkPostsResult::new(
+            rows.into_iter().map(|(id,)| id).collect(),
+            false,
+        ))
+    }
+
+    async fn create_user(&self, ctx: &Context<'_>, mut input: CreateUserInput) -> Result<User> {
+        let pool = ctx.data::<PgPool>()?;
+        input.validate().map_err(ApiError::Validation)?;
+        let CreateUserInput {
+            name,
+            age,
+            email,
+            phone,
+        } = input;
+        let phone = none_if_empty(phone);
+
+        let mut qb = QueryBuilder::<Postgres>::new(
+            "INSERT INTO users (name, age, email, phone, created_at, updated_at) VALUES (",
+        );
+        qb.push_bind(name)
+            .push(", ")
+            .push_bind(age)
+            .push(", ")
+            .push_bind(&email)
+            .push(", ")
+            .push_bind(phone)
+            .push(", NOW(), NOW()) ")
+            .push("RETURNING id, name, age, email, phone, created_at, updated_at");
+
+        let user = qb
+            .build_query_as::<User>()
+            .fetch_one(pool)
+            .await
+            .map_err(|e| user_write_error(Some(&email), e))?;
+
+        Ok(user)
+    }
+
+    async fn update_user(&self, ctx: &Context<'_>, mut input: UpdateUserInput) -> Result<User> {
+        let pool = ctx.data::<PgPool>()?;
+
+        if input.name.is_none()
+            && input.age.is_none()
+            && input.email.is_none()
+            && input.phone.is_none()
+        {
+            return Err(ApiError::invalid("input", "Nothing to update").into());
+        }
+
+        input.validate().map_err(ApiError::Validation)?;
+        let UpdateUserInput {
+            id,
+            name,
+            age,
+            email,
+            phone,
+        } = input;
+
+        let mut qb = QueryBuilder::<Postgres>::new("UPDATE users SET ");
+        if let Some(name) = name {
+            qb.push("name = ").push_bind(name).push(", ");
+        }
+        if let Some(age) = age {
+            qb.push("age = ").push_bind(age).push(", ");
+        }
+        if let Some(email) = &email {
+            qb.push("email = ").push_bind(email.clone()).push(", ");
+        }
+        if let Some(phone) = phone {
+            qb.push("phone = ")
+                .push_bind(none_if_empty(Some(phone)))
+                .push(", ");
+        }
+        qb.push("updated_at = NOW()");
+
+        qb.push(" WHERE id = ")
+            .push_bind(id)
+            .push(" RETURNING id, name, age, email, phone, created_at, updated_at");
+
+        let user = qb
+            .build_query_as::<User>()
+            .fetch_optional(pool)
+            .await
+            .map_err(|e| user_write_error(email.as_deref(), e))?
+            .ok_or_else(|| ApiError::NotFound(format!("User {} not found", id)))?;
+
+        Ok(user)
+    }
+
+    // Deletes a user, handling their posts as `posts` says (by default
+    // refusing while any live ones exist; trashed posts go with the user
+    // unless reassigned). Returns false when the user doesn't exist.
+    async fn delete_user(
+        &self,
+        ctx: &Context<'_>,
+        id: i32,
+        #[graphql(default)] posts: UserPostsOnDelete,
+        reassign_to: Option<i32>,
+    ) -> Result<bool> {
+        let pool = ctx.data::<PgPool>()?;
+
+        let reassign_to = match (posts, reassign_to) {
+            (UserPostsOnDelete::Reassign, Some(target)) if target == id => {
+                return Err(ApiError::invalid(
+                    "reassignTo",
+                    "Cannot reassign posts to the user being deleted",
+                )
+                .into());
+            }
+            (UserPostsOnDelete::Reassign, Some(target)) => Some(target),
+            (UserPostsOnDelete::Reassign, None) => {
+                return Err(ApiError::invalid(
+                    "reassignTo",
+                    "reassignTo is required when posts is REASSIGN",
+                )
+                .into());
+            }
+            (_, Some(_)) => {
+                return Err(ApiError::invalid(
+                    "reassignTo",
+                    "reassignTo is only allowed when posts is REASSIGN",
+                )
+                .into());
+            }
+            (_, None) => None,
+        };
+
+        let mut tx = pool.begin().await.map_err(ApiError::from)?;
+
+        // Locking the row also blocks new posts for it until we commit
+        let exists = sqlx::query("SELECT id FROM users WHERE id = $1 FOR UPDATE")
+            .bind(id)
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(ApiError::from)?
+            .is_some();
+        if !exists {
+            return Ok(false);
+        }
+
+        match reassign_to {
+            None if posts == UserPostsOnDelete::Refuse => {
+                let (count,) = sqlx::query_as::<_, (i32,)>(
+                    "SELECT COUNT(*)::int FROM posts WHERE user_id = $1 AND deleted_at IS NULL",
+                )
+                .bind(id)
+                .fetch_one(&mut *tx)
+                .await
+                .map_err(ApiError::from)?;
+                if count > 0 {
+                    return Err(ApiError::Conflict {
+                        field: Some("posts".to_string()),
+                        message: format!(
+                            "User {} has {} posts; delete with posts: CASCADE or REASSIGN",
+                            id, count
+                        ),
+                    }
+                    .into());
+                }
+            }
+            None => {
+                sqlx::query("DELETE FROM posts WHERE user_id = $1")
+                    .bind(id)
+                    .execute(&mut *tx)
+                    .await
+                    .map_err(ApiError::from)?;
+            }
+            Some(target) => {
+                let target_exists = sqlx::query("SELECT id FROM users WHERE id = $1 FOR SHARE")
+                    .bind(target)
+                    .fetch_optional(&mut *tx)
+                    .await
+                    .map_err(ApiError::from)?
+                    .is_some();
+                if !target_exists {
+                    return Err(ApiError::NotFound(format!(
+                        "User {} to reassign posts to not found",
+                        target
+                    ))
+                    .into());
+                }
+                sqlx::query("UPDATE posts SET user_id = $1 WHERE user_id = $2")
+                    .bind(target)
+                    .bind(id)
+                    .execute(&mut *tx)
+                    .await
+                    .map_err(ApiError::from)?;
+            }
+        }
+
+        sqlx::query("DELETE FROM users WHERE id = $1")
+            .bind(id)
+            .execute(&mut *tx)
+            .await
+            .map_err(ApiError::from)?;
+        tx.commit().await.map_err(ApiError::from)?;
+
+        Ok(true)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use proptest::collection::vec;
+    use proptest::option;
+    use proptest::prelude::*;
+
+    // Filters exercising column mapping, aliases and skipped fields
+    #[derive(FilterBuilder, Default)]
+    #[filter(table = "users", table_alias = "u")]
+    struct AliasedUserFilters {
+        id: Option<IntFilter>,
+        #[filter(column = "name")]
+        display_name: Option<StringFilter>,
+        #[filter(has_many(foreign_key = "user_id"))]
+        posts: Option<AliasedPostListFilter>,
+        #[filter(skip)]
+        #[allow(dead_code)]
+        client_tag: Option<String>,
+    }
+
+    #[derive(FilterBuilder, Default)]
+    #[filter(table = "posts", table_alias = "p")]
+    struct AliasedPostFilters {
+        #[filter(column = "u.email")]
+        author_email: Option<StringFilter>,
+        title: Option<StringFilter>,
+    }
+
+    #[derive(Default)]
+    struct AliasedPostListFilter {
+        some: Option<AliasedPostFilters>,
+        none: Option<AliasedPostFilters>,
+        every: Option<AliasedPostFilters>,
+    }
 
     #[test]
     fn test_user_filters_build_where_clause() {
@@ -417,20 +2048,14 @@ mod tests {
         let filters = UserFilters {
             id: Some(IntFilter {
                 equals: Some(1),
-                gt: None,
-                lt: None,
-                gte: None,
-                lte: None,
-            }),
-            name: None,
-            age: None,
-            email: None,
-            phone: None,
-            and: None,
-            or: None,
+                ..Default::default()
+            }),
+            ..Default::default()
         };
 
-        let (clause, params) = filters.build_where_clause();
+        let (clause, params) = filters
+            .build_where_clause(&FilterLimits::default())
+            .unwrap();
         assert!(clause.contains("WHERE"));
         assert!(clause.contains("id = $1"));
         assert_eq!(params.len(), 1);
@@ -442,26 +2067,18 @@ mod tests {
         let filters = UserFilters {
             id: Some(IntFilter {
                 equals: Some(1),
-                gt: None,
-                lt: None,
-                gte: None,
-                lte: None,
+                ..Default::default()
             }),
             name: Some(StringFilter {
                 equals: Some("Test".to_string()),
-                contains: None,
-                starts_with: None,
-                ends_with: None,
-                contains_insensitive: None,
+                ..Default::default()
             }),
-            age: None,
-            email: None,
-            phone: None,
-            and: None,
-            or: None,
+            ..Default::default()
         };
 
-        let (clause, params) = filters.build_where_clause();
+        let (clause, params) = filters
+            .build_where_clause(&FilterLimits::default())
+            .unwrap();
         assert!(clause.contains("id = $1"));
         assert!(clause.contains("name = $2"));
         assert_eq!(params.len(), 2);
@@ -471,22 +2088,17 @@ mod tests {
     fn test_user_filters_age_range() {
         // Verifies integer range filter behavior for UserFilters
         let filters = UserFilters {
-            id: None,
-            name: None,
             age: Some(IntFilter {
-                equals: None,
                 gt: Some(18),
                 lt: Some(65),
-                gte: None,
-                lte: None,
+                ..Default::default()
             }),
-            email: None,
-            phone: None,
-            and: None,
-            or: None,
+            ..Default::default()
         };
 
-        let (clause, params) = filters.build_where_clause();
+        let (clause, params) = filters
+            .build_where_clause(&FilterLimits::default())
+            .unwrap();
         assert!(clause.contains("age > $1"));
         assert!(clause.contains("age < $2"));
         assert_eq!(params.len(), 2);
@@ -496,41 +2108,29 @@ mod tests {
     fn test_user_filters_string_contains() {
         // Verifies string contains filter behavior for UserFilters
         let filters = UserFilters {
-            id: None,
-            name: None,
-            age: None,
             email: Some(StringFilter {
-                equals: None,
                 contains: Some("example".to_string()),
-                starts_with: None,
-                ends_with: None,
-                contains_insensitive: None,
+                ..Default::default()
             }),
-            phone: None,
-            and: None,
-            or: None,
+            ..Default::default()
         };
 
-        let (clause, params) = filters.build_where_clause();
+        let (clause, params) = filters
+            .build_where_clause(&FilterLimits::default())
+            .unwrap();
         assert!(clause.contains("email LIKE $1"));
         assert_eq!(params.len(), 1);
-        assert!(params[0].as_str().unwrap().contains("%example%"));
+        assert_eq!(params[0], SqlParam::Text("%example%".to_string()));
     }
 
     #[test]
     fn test_user_filters_empty() {
         // Verifies that empty filters produce no WHERE clause
-        let filters = UserFilters {
-            id: None,
-            name: None,
-            age: None,
-            email: None,
-            phone: None,
-            and: None,
-            or: None,
-        };
+        let filters = UserFilters::default();
 
-        let (clause, params) = filters.build_where_clause();
+        let (clause, params) = filters
+            .build_where_clause(&FilterLimits::default())
+            .unwrap();
         assert_eq!(clause, "");
         assert_eq!(params.len(), 0);
     }
@@ -541,18 +2141,14 @@ mod tests {
         let filters = PostFilters {
             id: Some(IntFilter {
                 equals: Some(5),
-                gt: None,
-                lt: None,
-                gte: None,
-                lte: None,
+                ..Default::default()
             }),
-            user_id: None,
-            title: None,
-            content: None,
-            or: None,
+            ..Default::default()
         };
 
-        let (clause, params) = filters.build_where_clause();
+        let (clause, params) = filters
+            .build_where_clause(&FilterLimits::default())
+            .unwrap();
         assert!(clause.contains("WHERE"));
         assert!(clause.contains("id = $1"));
         assert_eq!(params.len(), 1);
@@ -562,20 +2158,16 @@ mod tests {
     fn test_post_filters_title_contains() {
         // Verifies string contains filter behavior for PostFilters
         let filters = PostFilters {
-            id: None,
-            user_id: None,
             title: Some(StringFilter {
-                equals: None,
                 contains: Some("Rust".to_string()),
-                starts_with: None,
-                ends_with: None,
-                contains_insensitive: None,
+                ..Default::default()
             }),
-            content: None,
-            or: None,
+            ..Default::default()
         };
 
-        let (clause, params) = filters.build_where_clause();
+        let (clause, params) = filters
+            .build_where_clause(&FilterLimits::default())
+            .unwrap();
         assert!(clause.contains("title LIKE $1"));
         assert_eq!(params.len(), 1);
     }
@@ -584,22 +2176,16 @@ mod tests {
     fn test_string_filter_contains_insensitive() {
         // Verifies case-insensitive string contains filter behavior
         let filters = UserFilters {
-            id: None,
             name: Some(StringFilter {
-                equals: None,
-                contains: None,
-                starts_with: None,
-                ends_with: None,
                 contains_insensitive: Some("test".to_string()),
+                ..Default::default()
             }),
-            age: None,
-            email: None,
-            phone: None,
-            and: None,
-            or: None,
+            ..Default::default()
         };
 
-        let (clause, params) = filters.build_where_clause();
+        let (clause, params) = filters
+            .build_where_clause(&FilterLimits::default())
+            .unwrap();
         assert!(clause.contains("name ILIKE $1"));
         assert_eq!(params.len(), 1);
     }
@@ -608,131 +2194,235 @@ mod tests {
     fn test_int_filter_gte() {
         // Verifies integer greater-than-or-equal (>=) filter behavior
         let filters = UserFilters {
-            id: None,
-            name: None,
             age: Some(IntFilter {
-                equals: None,
-                gt: None,
-                lt: None,
                 gte: Some(18),
-                lte: None,
+                ..Default::default()
             }),
-            email: None,
-            phone: None,
-            and: None,
-            or: None,
+            ..Default::default()
         };
 
-        let (clause, params) = filters.build_where_clause();
+        let (clause, params) = filters
+            .build_where_clause(&FilterLimits::default())
+            .unwrap();
         assert!(clause.contains("age >= $1"));
         assert_eq!(params.len(), 1);
-        assert_eq!(params[0].as_i64().unwrap(), 18);
+        assert_eq!(params[0], SqlParam::Int(18));
     }
 
     #[test]
     fn test_int_filter_lte() {
         // Verifies integer less-than-or-equal (<=) filter behavior
         let filters = UserFilters {
-            id: None,
-            name: None,
             age: Some(IntFilter {
-                equals: None,
-                gt: None,
-                lt: None,
-                gte: None,
                 lte: Some(65),
+                ..Default::default()
             }),
-            email: None,
-            phone: None,
-            and: None,
-            or: None,
+            ..Default::default()
         };
 
-        let (clause, params) = filters.build_where_clause();
+        let (clause, params) = filters
+            .build_where_clause(&FilterLimits::default())
+            .unwrap();
         assert!(clause.contains("age <= $1"));
         assert_eq!(params.len(), 1);
-        assert_eq!(params[0].as_i64().unwrap(), 65);
+        assert_eq!(params[0], SqlParam::Int(65));
     }
 
     #[test]
     fn test_int_filter_gte_and_lte() {
         // Verifies integer range filter behavior with both gte and lte
         let filters = UserFilters {
-            id: None,
-            name: None,
             age: Some(IntFilter {
-                equals: None,
-                gt: None,
-                lt: None,
                 gte: Some(18),
                 lte: Some(65),
+                ..Default::default()
             }),
-            email: None,
-            phone: None,
-            and: None,
-            or: None,
+            ..Default::default()
         };
 
-        let (clause, params) = filters.build_where_clause();
+        let (clause, params) = filters
+            .build_where_clause(&FilterLimits::default())
+            .unwrap();
         assert!(clause.contains("age >= $1"));
         assert!(clause.contains("age <= $2"));
         assert_eq!(params.len(), 2);
-        assert_eq!(params[0].as_i64().unwrap(), 18);
-        assert_eq!(params[1].as_i64().unwrap(), 65);
+        assert_eq!(params[0], SqlParam::Int(18));
+        assert_eq!(params[1], SqlParam::Int(65));
     }
 
     #[test]
     fn test_string_filter_starts_with() {
         // Verifies WHERE clause generation for a single string starts_with filter
         let filters = UserFilters {
-            id: None,
             name: Some(StringFilter {
-                equals: None,
-                contains: None,
                 starts_with: Some("John".to_string()),
-                ends_with: None,
-                contains_insensitive: None,
+                ..Default::default()
             }),
-            age: None,
-            email: None,
-            phone: None,
-            and: None,
-            or: None,
+            ..Default::default()
         };
 
-        let (clause, params) = filters.build_where_clause();
+        let (clause, params) = filters
+            .build_where_clause(&FilterLimits::default())
+            .unwrap();
         assert!(clause.contains("name LIKE $1"));
         assert_eq!(params.len(), 1);
-        assert!(params[0].as_str().unwrap().starts_with("John"));
-        assert!(params[0].as_str().unwrap().ends_with("%"));
-        assert_eq!(params[0].as_str().unwrap(), "John%");
+        assert_eq!(params[0], SqlParam::Text("John%".to_string()));
     }
 
     #[test]
     fn test_string_filter_ends_with() {
         // Verifies WHERE clause generation for a single string ends_with filter
         let filters = UserFilters {
-            id: None,
-            name: None,
-            age: None,
             email: Some(StringFilter {
-                equals: None,
-                contains: None,
-                starts_with: None,
                 ends_with: Some(".com".to_string()),
-                contains_insensitive: None,
+                ..Default::default()
             }),
-            phone: None,
-            and: None,
-            or: None,
+            ..Default::default()
         };
 
-        let (clause, params) = filters.build_where_clause();
+        let (clause, params) = filters
+            .build_where_clause(&FilterLimits::default())
+            .unwrap();
         assert!(clause.contains("email LIKE $1"));
         assert_eq!(params.len(), 1);
-        assert!(params[0].as_str().unwrap().starts_with("%"));
-        assert!(params[0].as_str().unwrap().ends_with(".com"));
-        assert_eq!(params[0].as_str().unwrap(), "%.com");
+        assert_eq!(params[0], SqlParam::Text("%.com".to_string()));
+    }
+
+    #[test]
+    fn test_string_filter_date_like_value_binds_as_text() {
+        // Verifies a timestamp-shaped search string stays a text parameter
+        let filters = PostFilters {
+            title: Some(StringFilter {
+                equals: Some("2025-01-01T00:00:00Z".to_string()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let (clause, params) = filters
+            .build_where_clause(&FilterLimits::default())
+            .unwrap();
+        assert!(clause.contains("title = $1"));
+        assert_eq!(
+            params,
+            vec![SqlParam::Text("2025-01-01T00:00:00Z".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_datetime_filter_range() {
+        // Verifies date/time comparison and between operators for PostFilters
+        let from = "2025-01-01T00:00:00Z".parse::<DateTime<Utc>>().unwrap();
+        let to = "2025-01-08T00:00:00Z".parse::<DateTime<Utc>>().unwrap();
+        let filters = PostFilters {
+            created_at: Some(DateTimeFilter {
+                between: Some(DateTimeRange { from, to }),
+                ..Default::default()
+            }),
+            updated_at: Some(DateTimeFilter {
+                on_or_after: Some(from),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let (clause, params) = filters
+            .build_where_clause(&FilterLimits::default())
+            .unwrap();
+        assert!(clause.contains("created_at BETWEEN $1 AND $2"));
+        assert!(clause.contains("updated_at >= $3"));
+        assert_eq!(
+            params,
+            vec![
+                SqlParam::DateTime(from),
+                SqlParam::DateTime(to),
+                SqlParam::DateTime(from)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_int_filter_in_and_not_in() {
+        // Verifies list membership operators bind a single array parameter each
+        let filters = PostFilters {
+            user_id: Some(IntFilter {
+                in_list: Some(vec![1, 2, 3]),
+                ..Default::default()
+            }),
+            id: Some(IntFilter {
+                not_in: Some(vec![7]),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let (clause, params) = filters
+            .build_where_clause(&FilterLimits::default())
+            .unwrap();
+        assert!(clause.contains("id <> ALL($1)"));
+        assert!(clause.contains("user_id = ANY($2)"));
+        assert_eq!(
+            params,
+            vec![
+                SqlParam::IntArray(vec![7]),
+                SqlParam::IntArray(vec![1, 2, 3])
+            ]
+        );
+    }
+
+    #[test]
+    fn test_string_filter_in() {
+        // Verifies string list membership binds a text array
+        let filters = UserFilters {
+            email: Some(StringFilter {
+                in_list: Some(vec![
+                    "a@example.com".to_string(),
+                    "b@example.com".to_string(),
+                ]),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let (clause, params) = filters
+            .build_where_clause(&FilterLimits::default())
+            .unwrap();
+        assert!(clause.contains("email = ANY($1)"));
+        assert_eq!(
+            params,
+            vec![SqlParam::TextArray(vec![
+                "a@example.com".to_string(),
+                "b@example.com".to_string()
+            ])]
+        );
+    }
+
+    #[test]
+    fn test_filters_is_null() {
+        // Verifies isNull emits IS NULL / IS NOT NULL without binding parameters
+        let filters = PostFilters {
+            content: Some(StringFilter {
+                is_null: Some(true),
+                ..Default::default()
+            }),
+            user_id: Some(IntFilter {
+                is_null: Some(false),
+                ..Default::default()
+            }),
+            updated_at: Some(DateTimeFilter {
+                is_null: Some(false),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let (clause, params) = filters
+            .build_where_clause(&FilterLimits::default())
+            .unwrap();
+        assert!(clause.contains("content IS NULL"));
+        assert!(clause.contains("user_id IS NOT NULL"));
+        assert!(clause.contains("updated_at IS NOT NULL"));
+        assert_eq!(params.len(), 0);
     }
 
     #[test]
@@ -741,51 +2431,30 @@ mod tests {
         let filters = UserFilters {
             id: Some(IntFilter {
                 equals: Some(1),
-                gt: None,
-                lt: None,
-                gte: None,
-                lte: None,
-            }),
-            name: None,
-            age: None,
-            email: None,
-            phone: None,
-            and: None,
+                ..Default::default()
+            }),
             or: Some(vec![
                 UserFilters {
                     id: Some(IntFilter {
                         equals: Some(2),
-                        gt: None,
-                        lt: None,
-                        gte: None,
-                        lte: None,
+                        ..Default::default()
                     }),
-                    name: None,
-                    age: None,
-                    email: None,
-                    phone: None,
-                    and: None,
-                    or: None,
+                    ..Default::default()
                 },
                 UserFilters {
-                    id: None,
                     name: Some(StringFilter {
                         equals: Some("Test".to_string()),
-                        contains: None,
-                        starts_with: None,
-                        ends_with: None,
-                        contains_insensitive: None,
+                        ..Default::default()
                     }),
-                    age: None,
-                    email: None,
-                    phone: None,
-                    and: None,
-                    or: None,
+                    ..Default::default()
                 },
             ]),
+            ..Default::default()
         };
 
-        let (clause, params) = filters.build_where_clause();
+        let (clause, params) = filters
+            .build_where_clause(&FilterLimits::default())
+            .unwrap();
         assert!(clause.contains("WHERE"));
         assert!(clause.contains("id = $1"));
         assert!(clause.contains("OR"));
@@ -798,41 +2467,28 @@ mod tests {
     fn test_post_filters_or() {
         // Ensures PostFilters handle OR conditions
         let filters = PostFilters {
-            id: None,
-            user_id: None,
-            title: None,
-            content: None,
             or: Some(vec![
                 PostFilters {
                     id: Some(IntFilter {
                         equals: Some(1),
-                        gt: None,
-                        lt: None,
-                        gte: None,
-                        lte: None,
+                        ..Default::default()
                     }),
-                    user_id: None,
-                    title: None,
-                    content: None,
-                    or: None,
+                    ..Default::default()
                 },
                 PostFilters {
-                    id: None,
                     user_id: Some(IntFilter {
                         equals: Some(2),
-                        gt: None,
-                        lt: None,
-                        gte: None,
-                        lte: None,
+                        ..Default::default()
                     }),
-                    title: None,
-                    content: None,
-                    or: None,
+                    ..Default::default()
                 },
             ]),
+            ..Default::default()
         };
 
-        let (clause, params) = filters.build_where_clause();
+        let (clause, params) = filters
+            .build_where_clause(&FilterLimits::default())
+            .unwrap();
         assert!(clause.contains("WHERE"));
         assert!(clause.contains("OR"));
         assert!(clause.contains("(id = $1)"));
@@ -840,57 +2496,339 @@ mod tests {
         assert_eq!(params.len(), 2);
     }
 
+    #[test]
+    fn test_post_filters_not() {
+        // Verifies NOT wraps the negated filter and keeps parameter numbering
+        let filters = PostFilters {
+            user_id: Some(IntFilter {
+                equals: Some(1),
+                ..Default::default()
+            }),
+            not: Some(Box::new(PostFilters {
+                title: Some(StringFilter {
+                    contains: Some("Draft".to_string()),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            })),
+            ..Default::default()
+        };
+
+        let (clause, params) = filters
+            .build_where_clause(&FilterLimits::default())
+            .unwrap();
+        assert_eq!(
+            clause,
+            " WHERE user_id = $1 AND NOT (title LIKE $2 ESCAPE '\\')"
+        );
+        assert_eq!(params.len(), 2);
+    }
+
+    #[test]
+    fn test_post_filters_and() {
+        // Verifies PostFilters handle AND conditions
+        let filters = PostFilters {
+            and: Some(vec![
+                PostFilters {
+                    user_id: Some(IntFilter {
+                        equals: Some(1),
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                },
+                PostFilters {
+                    title: Some(StringFilter {
+                        starts_with: Some("Test".to_string()),
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                },
+            ]),
+            ..Default::default()
+        };
+
+        let (clause, params) = filters
+            .build_where_clause(&FilterLimits::default())
+            .unwrap();
+        assert_eq!(
+            clause,
+            " WHERE (user_id = $1) AND (title LIKE $2 ESCAPE '\\')"
+        );
+        assert_eq!(params.len(), 2);
+    }
+
+    #[test]
+    fn test_user_filters_or_is_grouped() {
+        // Verifies OR alternatives are parenthesized against sibling conditions
+        let filters = UserFilters {
+            age: Some(IntFilter {
+                gte: Some(18),
+                ..Default::default()
+            }),
+            or: Some(vec![
+                UserFilters {
+                    id: Some(IntFilter {
+                        equals: Some(1),
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                },
+                UserFilters {
+                    id: Some(IntFilter {
+                        equals: Some(2),
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                },
+            ]),
+            ..Default::default()
+        };
+
+        let (clause, _) = filters
+            .build_where_clause(&FilterLimits::default())
+            .unwrap();
+        assert_eq!(clause, " WHERE age >= $1 AND ((id = $2) OR (id = $3))");
+    }
+
+    #[test]
+    fn test_nested_placeholders_past_ten_params() {
+        // Verifies renumbering does not clobber two-digit placeholders
+        let mut filters = UserFilters {
+            id: Some(IntFilter {
+                equals: Some(1),
+                gt: Some(0),
+                lt: Some(100),
+                gte: Some(1),
+                lte: Some(99),
+                in_list: Some(vec![1]),
+                not_in: Some(vec![50]),
+                ..Default::default()
+            }),
+            age: Some(IntFilter {
+                gt: Some(1),
+                lt: Some(90),
+                ..Default::default()
+            }),
+            name: Some(StringFilter {
+                contains: Some("a".to_string()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        filters.or = Some(vec![UserFilters {
+            email: Some(StringFilter {
+                equals: Some("x".to_string()),
+                ends_with: Some(".com".to_string()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }]);
+
+        let (clause, params) = filters
+            .build_where_clause(&FilterLimits::default())
+            .unwrap();
+        assert_eq!(params.len(), 12);
+        assert!(clause.contains("((email = $11 AND email LIKE $12 ESCAPE '\\'))"));
+    }
+
+    #[test]
+    fn test_user_filters_posts_some() {
+        // Verifies a has-many relation compiles to a correlated EXISTS subquery
+        let filters = UserFilters {
+            age: Some(IntFilter {
+                gt: Some(20),
+                ..Default::default()
+            }),
+            posts: Some(PostListRelationFilter {
+                some: Some(PostFilters {
+                    title: Some(StringFilter {
+                        contains_insensitive: Some("rust".to_string()),
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let (clause, params) = filters
+            .build_where_clause(&FilterLimits::default())
+            .unwrap();
+        assert_eq!(
+            clause,
+            " WHERE age > $1 AND EXISTS (SELECT 1 FROM posts \
+             WHERE posts.user_id = users.id AND posts.deleted_at IS NULL \
+             AND (title ILIKE $2 ESCAPE '\\'))"
+        );
+        assert_eq!(params.len(), 2);
+    }
+
+    #[test]
+    fn test_user_filters_posts_none_and_every() {
+        // Verifies none/every relation operators and the empty-filter EXISTS form,
+        // both skipping trashed posts
+        let filters = UserFilters {
+            posts: Some(PostListRelationFilter {
+                none: Some(PostFilters::default()),
+                every: Some(PostFilters {
+                    content: Some(StringFilter {
+                        is_null: Some(false),
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let (clause, params) = filters
+            .build_where_clause(&FilterLimits::default())
+            .unwrap();
+        assert_eq!(
+            clause,
+            " WHERE NOT EXISTS (SELECT 1 FROM posts \
+             WHERE posts.user_id = users.id AND posts.deleted_at IS NULL) \
+             AND NOT EXISTS (SELECT 1 FROM posts \
+             WHERE posts.user_id = users.id AND posts.deleted_at IS NULL \
+             AND (NOT COALESCE((content IS NOT NULL), FALSE)))"
+        );
+        assert_eq!(params.len(), 0);
+    }
+
+    #[test]
+    fn test_post_filters_user_relation() {
+        // Verifies a belongs-to relation nests with OR and keeps parameter numbering
+        let filters = PostFilters {
+            id: Some(IntFilter {
+                gt: Some(1),
+                ..Default::default()
+            }),
+            user: Some(Box::new(UserFilters {
+                or: Some(vec![
+                    UserFilters {
+                        name: Some(StringFilter {
+                            equals: Some("A".to_string()),
+                            ..Default::default()
+                        }),
+                        ..Default::default()
+                    },
+                    UserFilters {
+                        age: Some(IntFilter {
+                            lt: Some(30),
+                            ..Default::default()
+                        }),
+                        ..Default::default()
+                    },
+                ]),
+                ..Default::default()
+            })),
+            ..Default::default()
+        };
+
+        let (clause, params) = filters
+            .build_where_clause(&FilterLimits::default())
+            .unwrap();
+        assert_eq!(
+            clause,
+            " WHERE id > $1 AND EXISTS (SELECT 1 FROM users \
+             WHERE users.id = posts.user_id AND (((name = $2) OR (age < $3))))"
+        );
+        assert_eq!(
+            params,
+            vec![
+                SqlParam::Int(1),
+                SqlParam::Text("A".to_string()),
+                SqlParam::Int(30)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_filter_column_mapping_and_alias() {
+        // Verifies column renames, alias qualification and skipped fields
+        let filters = AliasedUserFilters {
+            id: Some(IntFilter {
+                equals: Some(1),
+                ..Default::default()
+            }),
+            display_name: Some(StringFilter {
+                starts_with: Some("Jo".to_string()),
+                ..Default::default()
+            }),
+            client_tag: Some("ignored".to_string()),
+            ..Default::default()
+        };
+
+        let (clause, params) = filters
+            .build_where_clause(&FilterLimits::default())
+            .unwrap();
+        assert_eq!(clause, " WHERE u.id = $1 AND u.name LIKE $2 ESCAPE '\\'");
+        assert_eq!(params.len(), 2);
+    }
+
+    #[test]
+    fn test_filter_alias_in_relation_subquery() {
+        // Verifies relation subqueries use aliases and pre-qualified columns verbatim
+        let filters = AliasedUserFilters {
+            posts: Some(AliasedPostListFilter {
+                some: Some(AliasedPostFilters {
+                    title: Some(StringFilter {
+                        equals: Some("Rust".to_string()),
+                        ..Default::default()
+                    }),
+                    author_email: Some(StringFilter {
+                        is_null: Some(false),
+                        ..Default::default()
+                    }),
+                }),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let (clause, params) = filters
+            .build_where_clause(&FilterLimits::default())
+            .unwrap();
+        assert_eq!(
+            clause,
+            " WHERE EXISTS (SELECT 1 FROM posts p WHERE p.user_id = u.id \
+             AND (u.email IS NOT NULL AND p.title = $1))"
+        );
+        assert_eq!(params.len(), 1);
+    }
+
     #[test]
     fn test_user_filters_and() {
         // Verifies WHERE clause generation for AND conditions
         let filters = UserFilters {
             id: Some(IntFilter {
                 equals: Some(1),
-                gt: None,
-                lt: None,
-                gte: None,
-                lte: None,
-            }),
-            name: None,
-            age: None,
-            email: None,
-            phone: None,
+                ..Default::default()
+            }),
             and: Some(vec![
                 UserFilters {
-                    id: None,
                     name: Some(StringFilter {
                         equals: Some("John".to_string()),
-                        contains: None,
-                        starts_with: None,
-                        ends_with: None,
-                        contains_insensitive: None,
+                        ..Default::default()
                     }),
-                    age: None,
-                    email: None,
-                    phone: None,
-                    and: None,
-                    or: None,
+                    ..Default::default()
                 },
                 UserFilters {
-                    id: None,
-                    name: None,
                     age: Some(IntFilter {
                         equals: Some(25),
-                        gt: None,
-                        lt: None,
-                        gte: None,
-                        lte: None,
+                        ..Default::default()
                     }),
-                    email: None,
-                    phone: None,
-                    and: None,
-                    or: None,
+                    ..Default::default()
                 },
             ]),
-            or: None,
+            ..Default::default()
         };
 
-        let (clause, params) = filters.build_where_clause();
+        let (clause, params) = filters
+            .build_where_clause(&FilterLimits::default())
+            .unwrap();
         assert!(clause.contains("WHERE"));
         assert!(clause.contains("id = $1"));
         assert!(clause.contains("AND"));
@@ -898,4 +2836,765 @@ mod tests {
         assert!(clause.contains("(age = $3)"));
         assert_eq!(params.len(), 3);
     }
+
+    #[test]
+    fn test_order_by_default_and_tie_breaker() {
+        // Verifies default ordering and the id tie-breaker
+        assert_eq!(UserOrderBy::build_order_clause(&[]), " ORDER BY id ASC");
+        assert_eq!(
+            PostOrderBy::build_order_clause(&[]),
+            " ORDER BY created_at DESC, id ASC"
+        );
+    }
+
+    #[test]
+    fn test_order_by_multiple_columns() {
+        // Verifies entries apply in list order with direction and nulls placement
+        let order_by = vec![
+            UserOrderBy {
+                age: Some(SortOrder {
+                    direction: SortDirection::Desc,
+                    nulls: Some(NullsOrder::Last),
+                }),
+                ..Default::default()
+            },
+            UserOrderBy {
+                name: Some(SortOrder::default()),
+                ..Default::default()
+            },
+        ];
+
+        assert_eq!(
+            UserOrderBy::build_order_clause(&order_by),
+            " ORDER BY age DESC NULLS LAST, name ASC, id ASC"
+        );
+    }
+
+    #[test]
+    fn test_order_by_explicit_id_skips_tie_breaker() {
+        // Verifies an explicit id sort is not repeated by the tie-breaker
+        let order_by = vec![PostOrderBy {
+            id: Some(SortOrder {
+                direction: SortDirection::Desc,
+                nulls: None,
+            }),
+            ..Default::default()
+        }];
+
+        assert_eq!(
+            PostOrderBy::build_order_clause(&order_by),
+            " ORDER BY id DESC"
+        );
+    }
+
+    #[test]
+    fn test_keyset_condition_follows_sort_keys() {
+        // Verifies rows after a cursor respect direction, nulls placement and the id tie-breaker
+        let order_by = vec![UserOrderBy {
+            age: Some(SortOrder::default()),
+            ..Default::default()
+        }];
+        let keys = UserOrderBy::build_sort_keys(&order_by);
+
+        let values = vec![CursorValue::Int(30), CursorValue::Int(2)];
+        let (sql, params) = keyset_condition(&keys, &values).render(0);
+        assert_eq!(sql, "((age > $1 OR age IS NULL) OR (age = $2 AND id > $3))");
+        assert_eq!(
+            params,
+            vec![
+                SqlParam::BigInt(30),
+                SqlParam::BigInt(30),
+                SqlParam::BigInt(2)
+            ]
+        );
+
+        // ASC puts nulls last, so after a null age only later ids remain
+        let values = vec![CursorValue::Null, CursorValue::Int(2)];
+        let (sql, _) = keyset_condition(&keys, &values).render(0);
+        assert_eq!(sql, "(age IS NULL AND id > $1)");
+
+        // Walking backward flips both the direction and the nulls placement
+        let reversed: Vec<SortKey> = keys.iter().map(SortKey::reversed).collect();
+        let (sql, _) = keyset_condition(&reversed, &values).render(0);
+        assert_eq!(sql, "(age IS NOT NULL OR (age IS NULL AND id < $1))");
+        assert_eq!(
+            order_clause(&reversed),
+            " ORDER BY age DESC NULLS FIRST, id DESC NULLS FIRST"
+        );
+    }
+
+    #[test]
+    fn test_keyset_cursor_rejects_other_sort() {
+        // Verifies a cursor is only reused under the order it was issued for
+        let keys = PostOrderBy::build_sort_keys(&[]);
+        let cursor = KeysetCursor(vec![
+            ("created_at".to_string(), CursorValue::Null),
+            ("id".to_string(), CursorValue::Int(3)),
+        ]);
+        assert_eq!(
+            cursor.values_for(&keys),
+            Some(vec![CursorValue::Null, CursorValue::Int(3)])
+        );
+
+        let by_title = vec![PostOrderBy {
+            title: Some(SortOrder::default()),
+            ..Default::default()
+        }];
+        assert_eq!(
+            cursor.values_for(&PostOrderBy::build_sort_keys(&by_title)),
+            None
+        );
+    }
+
+    #[test]
+    fn test_string_filter_search() {
+        // Verifies full-text search compiles to the indexed tsvector expression
+        let filters = PostFilters {
+            content: Some(StringFilter {
+                search: Some("rust ownership".to_string()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let (clause, params) = filters
+            .build_where_clause(&FilterLimits::default())
+            .unwrap();
+        assert_eq!(
+            clause,
+            " WHERE to_tsvector('english', content) @@ websearch_to_tsquery('english', $1)"
+        );
+        assert_eq!(params, vec![SqlParam::Text("rust ownership".to_string())]);
+    }
+
+    #[test]
+    fn test_post_filters_content_search_term() {
+        // Verifies the ranking term is found at the top level or inside or/and
+        let filters = PostFilters {
+            or: Some(vec![
+                PostFilters {
+                    title: Some(StringFilter {
+                        contains_insensitive: Some("rust".to_string()),
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                },
+                PostFilters {
+                    content: Some(StringFilter {
+                        search: Some("rust".to_string()),
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                },
+            ]),
+            ..Default::default()
+        };
+        assert_eq!(filters.content_search(), Some("rust"));
+        assert_eq!(PostFilters::default().content_search(), None);
+    }
+
+    #[test]
+    fn test_post_filters_content_search_term_nested() {
+        // Verifies the ranking term is found at any and/or depth, but not under not
+        let search = |term: &str| PostFilters {
+            content: Some(StringFilter {
+                search: Some(term.to_string()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let filters = PostFilters {
+            not: Some(Box::new(search("excluded"))),
+            and: Some(vec![
+                PostFilters {
+                    user_id: Some(IntFilter {
+                        equals: Some(1),
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                },
+                PostFilters {
+                    or: Some(vec![PostFilters::default(), search("rust")]),
+                    ..Default::default()
+                },
+            ]),
+            ..Default::default()
+        };
+        assert_eq!(filters.content_search(), Some("rust"));
+
+        let negated_only = PostFilters {
+            not: Some(Box::new(search("excluded"))),
+            ..Default::default()
+        };
+        assert_eq!(negated_only.content_search(), None);
+    }
+
+    #[test]
+    fn test_string_filter_like_escapes_metacharacters() {
+        // Verifies %, _ and \ in LIKE patterns match literally
+        let filters = UserFilters {
+            email: Some(StringFilter {
+                contains: Some("first_last".to_string()),
+                ..Default::default()
+            }),
+            name: Some(StringFilter {
+                starts_with: Some("100%\\".to_string()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let (clause, params) = filters
+            .build_where_clause(&FilterLimits::default())
+            .unwrap();
+        assert_eq!(
+            clause,
+            " WHERE name LIKE $1 ESCAPE '\\' AND email LIKE $2 ESCAPE '\\'"
+        );
+        assert_eq!(
+            params,
+            vec![
+                SqlParam::Text("100\\%\\\\%".to_string()),
+                SqlParam::Text("%first\\_last%".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn test_string_filter_matches() {
+        // Verifies regex operators compile to ~ and ~* with the pattern bound as-is
+        let filters = PostFilters {
+            title: Some(StringFilter {
+                matches: Some("^Test Post [0-9]+$".to_string()),
+                ..Default::default()
+            }),
+            content: Some(StringFilter {
+                matches_insensitive: Some("rust|go".to_string()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let (clause, params) = filters
+            .build_where_clause(&FilterLimits::default())
+            .unwrap();
+        assert_eq!(clause, " WHERE title ~ $1 AND content ~* $2");
+        assert_eq!(
+            params,
+            vec![
+                SqlParam::Text("^Test Post [0-9]+$".to_string()),
+                SqlParam::Text("rust|go".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn test_string_filter_insensitive_mode() {
+        // Verifies mode: INSENSITIVE lowers both sides so LOWER(...) indexes apply
+        let filters = UserFilters {
+            email: Some(StringFilter {
+                equals: Some("Test1@Example.com".to_string()),
+                mode: Some(StringMode::Insensitive),
+                ..Default::default()
+            }),
+            name: Some(StringFilter {
+                starts_with: Some("test".to_string()),
+                not_in: Some(vec!["TEST USER 3".to_string()]),
+                matches: Some("user [12]$".to_string()),
+                mode: Some(StringMode::Insensitive),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let (clause, params) = filters
+            .build_where_clause(&FilterLimits::default())
+            .unwrap();
+        assert_eq!(
+            clause,
+            " WHERE LOWER(name) LIKE LOWER($1) ESCAPE '\\' \
+             AND LOWER(name) <> ALL(SELECT LOWER(v) FROM unnest($2) AS v) \
+             AND name ~* $3 AND LOWER(email) = LOWER($4)"
+        );
+        assert_eq!(params.len(), 4);
+    }
+
+    #[test]
+    fn test_string_filter_similar_to() {
+        // Verifies trigram matching prefilters with % and checks the threshold
+        // with similarity(), which defaults to 0.3 and drops the % below 0.1
+        let filters = UserFilters {
+            name: Some(StringFilter {
+                similar_to: Some(SimilarityInput {
+                    value: "Jon Doe".to_string(),
+                    threshold: None,
+                }),
+                ..Default::default()
+            }),
+            or: Some(vec![
+                UserFilters {
+                    email: Some(StringFilter {
+                        similar_to: Some(SimilarityInput {
+                            value: "smyth".to_string(),
+                            threshold: Some(0.2),
+                        }),
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                },
+                UserFilters {
+                    phone: Some(StringFilter {
+                        similar_to: Some(SimilarityInput {
+                            value: "555".to_string(),
+                            threshold: Some(0.05),
+                        }),
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                },
+            ]),
+            ..Default::default()
+        };
+
+        let (clause, params) = filters
+            .build_where_clause(&FilterLimits::default())
+            .unwrap();
+        assert_eq!(
+            clause,
+            " WHERE name % $1 AND similarity(name, $2) >= $3 AND \
+             ((email % $4 AND similarity(email, $5) >= $6) OR (similarity(phone, $7) >= $8))"
+        );
+        assert_eq!(
+            params,
+            vec![
+                SqlParam::Text("Jon Doe".to_string()),
+                SqlParam::Text("Jon Doe".to_string()),
+                SqlParam::Float(0.3),
+                SqlParam::Text("smyth".to_string()),
+                SqlParam::Text("smyth".to_string()),
+                SqlParam::Float(0.2),
+                SqlParam::Text("555".to_string()),
+                SqlParam::Float(0.05)
+            ]
+        );
+        assert_eq!(filters.name_similarity(), Some("Jon Doe"));
+    }
+
+    #[test]
+    fn test_int_filter_between() {
+        // Verifies between is inclusive on both ends and combines with other operators
+        let filters = UserFilters {
+            age: Some(IntFilter {
+                between: Some(IntRange { from: 18, to: 30 }),
+                not_in: Some(vec![21]),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let (clause, params) = filters
+            .build_where_clause(&FilterLimits::default())
+            .unwrap();
+        assert_eq!(clause, " WHERE age <> ALL($1) AND age BETWEEN $2 AND $3");
+        assert_eq!(
+            params,
+            vec![
+                SqlParam::IntArray(vec![21]),
+                SqlParam::Int(18),
+                SqlParam::Int(30)
+            ]
+        );
+    }
+
+    const UNLIMITED: FilterLimits = FilterLimits {
+        max_depth: usize::MAX,
+        max_conditions: usize::MAX,
+        max_list_length: usize::MAX,
+    };
+
+    // `depth` levels of NOT wrapped around a single condition
+    fn nested_not(depth: usize) -> UserFilters {
+        let mut filters = UserFilters {
+            id: Some(IntFilter {
+                equals: Some(1),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        for _ in 1..depth {
+            filters = UserFilters {
+                not: Some(Box::new(filters)),
+                ..Default::default()
+            };
+        }
+        filters
+    }
+
+    #[test]
+    fn test_filter_depth_limit() {
+        // Verifies the top level counts as depth 1 and relations add a level
+        let limits = FilterLimits {
+            max_depth: 3,
+            ..Default::default()
+        };
+        assert!(nested_not(3).build_where_clause(&limits).is_ok());
+        assert_eq!(
+            nested_not(4).build_where_clause(&limits),
+            Err(FilterLimitError::Depth(3))
+        );
+
+        let filters = PostFilters {
+            user: Some(Box::new(UserFilters {
+                posts: Some(PostListRelationFilter {
+                    some: Some(PostFilters {
+                        id: Some(IntFilter {
+                            equals: Some(1),
+                            ..Default::default()
+                        }),
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            })),
+            ..Default::default()
+        };
+        assert!(
+            filters
+                .build_where_clause(&FilterLimits {
+                    max_depth: 2,
+                    ..Default::default()
+                })
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_filter_condition_limit() {
+        // Verifies conditions are counted across every nested filter
+        let filters = UserFilters {
+            or: Some(
+                (1..=3)
+                    .map(|id| UserFilters {
+                        id: Some(IntFilter {
+                            equals: Some(id),
+                            ..Default::default()
+                        }),
+                        ..Default::default()
+                    })
+                    .collect(),
+            ),
+            ..Default::default()
+        };
+        let limits = FilterLimits {
+            max_conditions: 3,
+            ..Default::default()
+        };
+        assert_eq!(
+            filters.build_where_clause(&limits),
+            Err(FilterLimitError::Conditions(3))
+        );
+        let limits = FilterLimits {
+            max_conditions: 4,
+            ..Default::default()
+        };
+        assert!(filters.build_where_clause(&limits).is_ok());
+    }
+
+    #[test]
+    fn test_filter_list_length_limit() {
+        // Verifies in/notIn values and and/or entries are bounded
+        let limits = FilterLimits {
+            max_list_length: 2,
+            ..Default::default()
+        };
+        let filters = UserFilters {
+            name: Some(StringFilter {
+                not_in: Some(vec!["a".into(), "b".into(), "c".into()]),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        assert_eq!(
+            filters.build_where_clause(&limits),
+            Err(FilterLimitError::ListLength(2))
+        );
+
+        let filters = UserFilters {
+            and: Some((0..3).map(|_| UserFilters::default()).collect()),
+            ..Default::default()
+        };
+        let err = filters.build_where_clause(&limits).unwrap_err();
+        assert_eq!(err.limit_name(), "maxListLength");
+        assert_eq!(err.limit(), 2);
+    }
+
+    #[test]
+    fn test_api_error_extensions() {
+        let err: async_graphql::Error = ApiError::Conflict {
+            field: Some("email".to_string()),
+            message: "Email is already in use: a@b.co".to_string(),
+        }
+        .into();
+        assert_eq!(err.message, "Email is already in use: a@b.co");
+        let extensions = err.extensions.unwrap();
+        assert_eq!(extensions.get("code"), Some(&"CONFLICT".into()));
+        assert_eq!(extensions.get("field"), Some(&"email".into()));
+
+        let err: async_graphql::Error = ApiError::Validation(vec![
+            FieldError {
+                field: "name".to_string(),
+                message: "Name cannot be empty".to_string(),
+                limit: None,
+            },
+            FieldError {
+                field: "age".to_string(),
+                message: "Age must be between 0 and 150".to_string(),
+                limit: None,
+            },
+        ])
+        .into();
+        assert_eq!(
+            err.message,
+            "Name cannot be empty; Age must be between 0 and 150"
+        );
+        assert_eq!(
+            err.extensions.unwrap().get("fields"),
+            Some(&value!([
+                { "field": "name", "message": "Name cannot be empty" },
+                { "field": "age", "message": "Age must be between 0 and 150" },
+            ]))
+        );
+    }
+
+    #[test]
+    fn test_internal_error_hides_details() {
+        let err: async_graphql::Error =
+            ApiError::from(sqlx::Error::Protocol("secret detail".to_string())).into();
+        assert_eq!(err.message, "Internal server error");
+        assert_eq!(
+            err.extensions.unwrap().get("code"),
+            Some(&"INTERNAL".into())
+        );
+        assert_eq!(ApiError::from(sqlx::Error::RowNotFound).code(), "NOT_FOUND");
+        assert_eq!(column_field("user_id"), "userId");
+        assert_eq!(column_field("title"), "title");
+    }
+
+    #[test]
+    fn test_validate_email() {
+        assert!(validate_email("jane@example.co.uk").is_ok());
+        for email in [
+            "",
+            "jane",
+            "@example.com",
+            "jane@",
+            "jane@example",
+            "jane@.com",
+            "jane@example.",
+            "jane@@example.com",
+            "ja ne@example.com",
+        ] {
+            assert!(validate_email(email).is_err(), "{email}");
+        }
+    }
+
+    #[test]
+    fn test_validate_phone() {
+        assert!(validate_phone("111-111-1111").is_ok());
+        assert!(validate_phone("+1 (415) 600.9080").is_ok());
+        assert!(validate_phone("").is_ok());
+        for phone in ["12345", "1234567890123456", "555-CALL-NOW", "1+5551234567"] {
+            assert!(validate_phone(phone).is_err(), "{phone}");
+        }
+    }
+
+    fn create_user_input(name: &str, age: i32, email: &str) -> CreateUserInput {
+        CreateUserInput {
+            name: name.to_string(),
+            age,
+            email: email.to_string(),
+            phone: None,
+        }
+    }
+
+    #[test]
+    fn test_validate_trims_in_place() {
+        let mut input = create_user_input("  Jane Doe ", 30, " jane@example.com ");
+        input.phone = Some(" 555-123-4567\n".to_string());
+        assert_eq!(input.validate(), Ok(()));
+        assert_eq!(input.name, "Jane Doe");
+        assert_eq!(input.email, "jane@example.com");
+        assert_eq!(input.phone.as_deref(), Some("555-123-4567"));
+    }
+
+    #[test]
+    fn test_validate_reports_every_invalid_field() {
+        let mut input = create_user_input("   ", 151, "jane");
+        input.phone = Some("call me".to_string());
+        assert_eq!(
+            input.validate(),
+            Err(vec![
+                FieldError::new("name", "Name cannot be empty"),
+                FieldError::new("age", "Age must be between 0 and 150"),
+                FieldError::new("email", "Invalid email address: jane"),
+                FieldError::new("phone", "Invalid phone number: call me"),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_validate_bounds() {
+        assert_eq!(create_user_input("A", 0, "a@b.co").validate(), Ok(()));
+        assert_eq!(create_user_input("A", 150, "a@b.co").validate(), Ok(()));
+        assert_eq!(
+            create_user_input(&"x".repeat(256), -1, "a@b.co").validate(),
+            Err(vec![
+                FieldError::new("name", "Name cannot be longer than 255 characters"),
+                FieldError::new("age", "Age must be between 0 and 150"),
+            ])
+        );
+
+        // Lengths count characters, not bytes
+        let mut input = CreatePostInput {
+            user_id: 1,
+            title: "é".repeat(MAX_TITLE_LENGTH),
+            content: None,
+        };
+        assert_eq!(input.validate(), Ok(()));
+        input.title.push('x');
+        assert_eq!(
+            input.validate(),
+            Err(vec![FieldError::new(
+                "title",
+                "Title cannot be longer than 50 characters"
+            )])
+        );
+    }
+
+    #[test]
+    fn test_validate_skips_omitted_fields() {
+        let mut input = UpdatePostInput {
+            id: 1,
+            title: None,
+            content: Some("  ".to_string()),
+        };
+        assert_eq!(input.validate(), Ok(()));
+        assert_eq!(input.content.as_deref(), Some(""));
+
+        input.title = Some(" \t ".to_string());
+        assert_eq!(
+            input.validate(),
+            Err(vec![FieldError::new("title", "Title cannot be empty")])
+        );
+    }
+
+    // Placeholder numbers in order of appearance
+    fn placeholders(sql: &str) -> Vec<usize> {
+        let mut found = Vec::new();
+        let mut chars = sql.chars().peekable();
+        while let Some(c) = chars.next() {
+            if c == '$' && chars.peek().is_some_and(|d| d.is_ascii_digit()) {
+                let mut index = 0usize;
+                while let Some(digit) = chars.peek().and_then(|d| d.to_digit(10)) {
+                    index = index * 10 + digit as usize;
+                    chars.next();
+                }
+                found.push(index);
+            }
+        }
+        found
+    }
+
+    fn text() -> impl Strategy<Value = String> {
+        // Includes `$`, `%` and `_` to show values never leak into the SQL
+        "[a-z$%_0-9 ]{0,6}"
+    }
+
+    fn int_filter() -> impl Strategy<Value = IntFilter> {
+        (
+            option::of(any::<i32>()),
+            option::of(any::<i32>()),
+            option::of(vec(any::<i32>(), 0..3)),
+            option::of(any::<bool>()),
+        )
+            .prop_map(|(equals, gt, in_list, is_null)| IntFilter {
+                equals,
+                gt,
+                in_list,
+                is_null,
+                ..Default::default()
+            })
+    }
+
+    fn string_filter() -> impl Strategy<Value = StringFilter> {
+        (
+            option::of(text()),
+            option::of(text()),
+            option::of(vec(text(), 0..3)),
+            option::of(text()),
+            option::of((text(), option::of(0.0..1.0f64))),
+            any::<bool>(),
+        )
+            .prop_map(
+                |(equals, starts_with, not_in, search, similar, insensitive)| StringFilter {
+                    equals,
+                    starts_with,
+                    not_in,
+                    search,
+                    similar_to: similar
+                        .map(|(value, threshold)| SimilarityInput { value, threshold }),
+                    mode: insensitive.then_some(StringMode::Insensitive),
+                    ..Default::default()
+                },
+            )
+    }
+
+    // Random UserFilters trees nesting and/or/not and posts -> user relations
+    fn user_filters() -> impl Strategy<Value = UserFilters> {
+        let leaf = (
+            option::of(int_filter()),
+            option::of(string_filter()),
+            option::of(string_filter()),
+        )
+            .prop_map(|(age, name, email)| UserFilters {
+                age,
+                name,
+                email,
+                ..Default::default()
+            });
+        leaf.prop_recursive(5, 64, 3, |inner| {
+            (
+                inner.clone(),
+                option::of(vec(inner.clone(), 0..3)),
+                option::of(vec(inner.clone(), 0..3)),
+                option::of(inner.clone()),
+                option::of((option::of(string_filter()), inner)),
+            )
+                .prop_map(|(mut filters, and, or, not, posts)| {
+                    filters.and = and;
+                    filters.or = or;
+                    filters.not = not.map(Box::new);
+                    filters.posts = posts.map(|(title, user)| PostListRelationFilter {
+                        every: Some(PostFilters {
+                            title,
+                            user: Some(Box::new(user)),
+                            ..Default::default()
+                        }),
+                        ..Default::default()
+                    });
+                    filters
+                })
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn test_nested_filters_number_every_param_once(filters in user_filters()) {
+            // Verifies placeholders run $1..$n in order, one per bound parameter
+            let (clause, params) = filters.build_where_clause(&UNLIMITED).unwrap();
+            let expected: Vec<usize> = (1..=params.len()).collect();
+            prop_assert_eq!(placeholders(&clause), expected);
+        }
+    }
 }
@@ -1,6 +1,8 @@
 // This file contains test utilities for setting up and managing test environments.
 
-use async_graphql::{EmptySubscription, Schema};
+use crate::resolvers::{PostLoader, PostsCountLoader, UserLoader, UserPostsLoader};
+use crate::utils::connect_options;
+use async_graphql::{EmptySubscription, Schema, dataloader::DataLoader};
 use sqlx::{PgPool, postgres::PgPoolOptions};
 use std::env;
 
@@ -13,7 +15,7 @@ pub async fn init_test_db() -> Result<PgPool, sqlx::Error> {
 
     let pool = PgPoolOptions::new()
         .max_connections(5)
-        .connect(&database_url)
+        .connect_with(connect_options(&database_url)?)
         .await?;
 
     Ok(pool)
@@ -43,13 +45,17 @@ pub async fn setup_test_schema(pool: &PgPool) -> Result<(), sqlx::Error> {
     // Wait for locks to clear
     tokio::time::sleep(tokio::time::Duration::from_millis(200)).await;
 
+    sqlx::query("CREATE EXTENSION IF NOT EXISTS pg_trgm")
+        .execute(pool)
+        .await?;
+
     sqlx::query(
         r#"
         CREATE TABLE users (
             id SERIAL PRIMARY KEY,
             name VARCHAR(255),
             age INTEGER,
-            email VARCHAR(255),
+            email VARCHAR(255) UNIQUE,
             phone VARCHAR(50),
             created_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
             updated_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP
@@ -59,6 +65,10 @@ pub async fn setup_test_schema(pool: &PgPool) -> Result<(), sqlx::Error> {
     .execute(pool)
     .await?;
 
+    sqlx::query("CREATE UNIQUE INDEX users_email_lower_key ON users (LOWER(email))")
+        .execute(pool)
+        .await?;
+
     sqlx::query(
         r#"
         CREATE TABLE posts (
@@ -67,7 +77,8 @@ pub async fn setup_test_schema(pool: &PgPool) -> Result<(), sqlx::Error> {
             title VARCHAR(255) NOT NULL,
             content TEXT,
             created_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
-            updated_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP
+            updated_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+            deleted_at TIMESTAMP WITH TIME ZONE
         )
         "#,
     )
@@ -250,7 +261,7 @@ pub async fn setup() -> PgPool {
     pool
 }
 
-// Helper function to build GraphQL schema
+// Helper function to build GraphQL schema, with the same loaders as main.rs
 // Note: This requires resolvers module to be included in the test file
 pub fn build_schema<Query, Mutation>(
     pool: PgPool,
@@ -262,6 +273,26 @@ where
     Mutation: async_graphql::ObjectType + Send + Sync + 'static,
 {
     Schema::build(query, mutation, EmptySubscription)
+        .data(DataLoader::new(UserLoader::new(pool.clone()), tokio::spawn))
+        .data(DataLoader::new(PostLoader::new(pool.clone()), tokio::spawn))
+        .data(DataLoader::new(
+            UserPostsLoader::new(pool.clone()),
+            tokio::spawn,
+        ))
+        .data(DataLoader::new(
+            PostsCountLoader::new(pool.clone()),
+            tokio::spawn,
+        ))
         .data(pool)
         .finish()
 }
+
+// `extensions.<key>` of the first error
+#[allow(dead_code)]
+pub fn error_extension(result: &async_graphql::Response, key: &str) -> async_graphql::Value {
+    let extensions = result.errors[0].extensions.as_ref().unwrap();
+    extensions
+        .get(key)
+        .cloned()
+        .unwrap_or(async_graphql::Value::Null)
+}
@@ -1,49 +1,786 @@
+use base64::{Engine, engine::general_purpose::STANDARD};
 use chrono::{DateTime, Utc};
-use serde_json::Value;
-use sqlx::{Postgres, postgres::PgArguments, query::QueryAs};
+use serde::{Deserialize, Serialize};
+use sqlx::{
+    Postgres,
+    postgres::{PgArguments, PgConnectOptions},
+    query::QueryAs,
+};
+use std::{env, fmt};
 
-// Dynamically binds JSON values (numbers, strings, dates, bools, etc.)
-// to a SQLx query safely and type-correctly.
-pub fn bind_dynamic_params<'q, T>(
-    mut query: QueryAs<'q, Postgres, T, PgArguments>,
-    params: Vec<Value>,
-) -> QueryAs<'q, Postgres, T, PgArguments> {
-    for p in params {
-        match p {
-            // --- Numbers ---
-            Value::Number(n) => {
-                if let Some(i) = n.as_i64() {
-                    query = query.bind(i as i32);
-                } else if let Some(f) = n.as_f64() {
-                    query = query.bind(f);
-                }
+// A typed SQL parameter produced by the FilterBuilder derive.
+// Each variant carries the exact Rust type declared on the filter input,
+// so binding never has to guess at the Postgres type.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SqlParam {
+    Int(i32),
+    BigInt(i64),
+    Float(f64),
+    Text(String),
+    Bool(bool),
+    DateTime(DateTime<Utc>),
+    IntArray(Vec<i32>),
+    BigIntArray(Vec<i64>),
+    FloatArray(Vec<f64>),
+    TextArray(Vec<String>),
+    BoolArray(Vec<bool>),
+    DateTimeArray(Vec<DateTime<Utc>>),
+}
+
+impl From<i32> for SqlParam {
+    fn from(value: i32) -> Self {
+        SqlParam::Int(value)
+    }
+}
+
+impl From<i64> for SqlParam {
+    fn from(value: i64) -> Self {
+        SqlParam::BigInt(value)
+    }
+}
+
+impl From<f64> for SqlParam {
+    fn from(value: f64) -> Self {
+        SqlParam::Float(value)
+    }
+}
+
+impl From<String> for SqlParam {
+    fn from(value: String) -> Self {
+        SqlParam::Text(value)
+    }
+}
+
+impl From<&str> for SqlParam {
+    fn from(value: &str) -> Self {
+        SqlParam::Text(value.to_string())
+    }
+}
+
+impl From<bool> for SqlParam {
+    fn from(value: bool) -> Self {
+        SqlParam::Bool(value)
+    }
+}
+
+impl From<DateTime<Utc>> for SqlParam {
+    fn from(value: DateTime<Utc>) -> Self {
+        SqlParam::DateTime(value)
+    }
+}
+
+impl From<Vec<i32>> for SqlParam {
+    fn from(value: Vec<i32>) -> Self {
+        SqlParam::IntArray(value)
+    }
+}
+
+impl From<Vec<i64>> for SqlParam {
+    fn from(value: Vec<i64>) -> Self {
+        SqlParam::BigIntArray(value)
+    }
+}
+
+impl From<Vec<f64>> for SqlParam {
+    fn from(value: Vec<f64>) -> Self {
+        SqlParam::FloatArray(value)
+    }
+}
+
+impl From<Vec<String>> for SqlParam {
+    fn from(value: Vec<String>) -> Self {
+        SqlParam::TextArray(value)
+    }
+}
+
+impl From<Vec<bool>> for SqlParam {
+    fn from(value: Vec<bool>) -> Self {
+        SqlParam::BoolArray(value)
+    }
+}
+
+impl From<Vec<DateTime<Utc>>> for SqlParam {
+    fn from(value: Vec<DateTime<Utc>>) -> Self {
+        SqlParam::DateTimeArray(value)
+    }
+}
+
+// A WHERE-clause fragment made of SQL text and bound parameters.
+// Placeholders are only numbered when the finished clause is rendered,
+// so nested clauses are combined structurally and their SQL is never rewritten.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Clause {
+    parts: Vec<ClausePart>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum ClausePart {
+    Sql(String),
+    Param(SqlParam),
+}
+
+impl Clause {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Clause holding only SQL text
+    pub fn raw(sql: &str) -> Self {
+        let mut clause = Self::new();
+        clause.push(sql);
+        clause
+    }
+
+    // Appends SQL text
+    pub fn push(&mut self, sql: &str) -> &mut Self {
+        if sql.is_empty() {
+            return self;
+        }
+        match self.parts.last_mut() {
+            Some(ClausePart::Sql(last)) => last.push_str(sql),
+            _ => self.parts.push(ClausePart::Sql(sql.to_string())),
+        }
+        self
+    }
+
+    // Appends a placeholder bound to `param`
+    pub fn push_bind(&mut self, param: impl Into<SqlParam>) -> &mut Self {
+        self.parts.push(ClausePart::Param(param.into()));
+        self
+    }
+
+    // Appends another clause with its parameters
+    pub fn push_clause(&mut self, clause: Clause) -> &mut Self {
+        for part in clause.parts {
+            match part {
+                ClausePart::Sql(sql) => self.push(&sql),
+                ClausePart::Param(param) => self.push_bind(param),
+            };
+        }
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parts.is_empty()
+    }
+
+    // `(clause)`
+    pub fn parenthesized(self) -> Self {
+        let mut clause = Self::raw("(");
+        clause.push_clause(self).push(")");
+        clause
+    }
+
+    // Joins clauses with `separator` (e.g. " AND "), skipping empty ones
+    pub fn join(clauses: Vec<Clause>, separator: &str) -> Self {
+        let mut joined = Self::new();
+        for clause in clauses.into_iter().filter(|c| !c.is_empty()) {
+            if !joined.is_empty() {
+                joined.push(separator);
             }
+            joined.push_clause(clause);
+        }
+        joined
+    }
 
-            // --- Strings (try for date) ---
-            Value::String(s) => {
-                if let Ok(dt) = DateTime::parse_from_rfc3339(&s) {
-                    query = query.bind(dt.with_timezone(&Utc));
-                } else {
-                    query = query.bind(s);
+    // Renders the SQL with placeholders numbered from `$base + 1`,
+    // returning the parameters in placeholder order
+    pub fn render(self, base: usize) -> (String, Vec<SqlParam>) {
+        let mut sql = String::new();
+        let mut params = Vec::new();
+        for part in self.parts {
+            match part {
+                ClausePart::Sql(text) => sql.push_str(&text),
+                ClausePart::Param(param) => {
+                    params.push(param);
+                    sql.push_str(&format!("${}", base + params.len()));
                 }
             }
+        }
+        (sql, params)
+    }
+}
 
-            // --- Booleans ---
-            Value::Bool(b) => {
-                query = query.bind(b);
-            }
+// Bounds on the filters a single request may submit, checked while the
+// generated `build_where_clause` walks the filter tree
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FilterLimits {
+    // Levels of and/or/not and relation nesting, counting the top level as 1
+    pub max_depth: usize,
+    // Conditions across the whole tree
+    pub max_conditions: usize,
+    // Entries in any and/or list or in/notIn list
+    pub max_list_length: usize,
+}
+
+impl Default for FilterLimits {
+    fn default() -> Self {
+        FilterLimits {
+            max_depth: 10,
+            max_conditions: 100,
+            max_list_length: 500,
+        }
+    }
+}
 
-            // --- Nulls ---
-            Value::Null => {
-                query = query.bind::<Option<String>>(None);
+impl FilterLimits {
+    // Reads FILTER_MAX_DEPTH, FILTER_MAX_CONDITIONS and FILTER_MAX_LIST_LENGTH,
+    // keeping the default for any that are unset or invalid. Only the server
+    // binary calls this; integration tests include this file without it
+    #[allow(dead_code)]
+    pub fn from_env() -> Self {
+        let defaults = FilterLimits::default();
+        let read = |name: &str, default: usize| {
+            env::var(name)
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(default)
+        };
+        FilterLimits {
+            max_depth: read("FILTER_MAX_DEPTH", defaults.max_depth),
+            max_conditions: read("FILTER_MAX_CONDITIONS", defaults.max_conditions),
+            max_list_length: read("FILTER_MAX_LIST_LENGTH", defaults.max_list_length),
+        }
+    }
+}
+
+// Days a post stays in the trash before the server purges it for good
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TrashRetention {
+    pub days: i32,
+}
+
+impl Default for TrashRetention {
+    fn default() -> Self {
+        TrashRetention { days: 30 }
+    }
+}
+
+impl TrashRetention {
+    // Reads POST_TRASH_RETENTION_DAYS, keeping the default when it is unset,
+    // invalid or negative
+    #[allow(dead_code)]
+    pub fn from_env() -> Self {
+        env::var("POST_TRASH_RETENTION_DAYS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .filter(|days| *days >= 0)
+            .map(|days| TrashRetention { days })
+            .unwrap_or_default()
+    }
+}
+
+// The filter limit a request exceeded
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FilterLimitError {
+    Depth(usize),
+    Conditions(usize),
+    ListLength(usize),
+}
+
+impl FilterLimitError {
+    // Name of the exceeded limit, as reported to clients
+    pub fn limit_name(&self) -> &'static str {
+        match self {
+            FilterLimitError::Depth(_) => "maxDepth",
+            FilterLimitError::Conditions(_) => "maxConditions",
+            FilterLimitError::ListLength(_) => "maxListLength",
+        }
+    }
+
+    pub fn limit(&self) -> usize {
+        match self {
+            FilterLimitError::Depth(limit)
+            | FilterLimitError::Conditions(limit)
+            | FilterLimitError::ListLength(limit) => *limit,
+        }
+    }
+}
+
+impl fmt::Display for FilterLimitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FilterLimitError::Depth(limit) => {
+                write!(f, "Filter nesting exceeds the maximum depth of {}", limit)
+            }
+            FilterLimitError::Conditions(limit) => {
+                write!(
+                    f,
+                    "Filter has more than the maximum of {} conditions",
+                    limit
+                )
             }
+            FilterLimitError::ListLength(limit) => {
+                write!(
+                    f,
+                    "Filter list has more than the maximum of {} entries",
+                    limit
+                )
+            }
+        }
+    }
+}
+
+// Running tally of one filter tree against its limits
+pub struct FilterBudget<'a> {
+    limits: &'a FilterLimits,
+    conditions: usize,
+}
+
+impl<'a> FilterBudget<'a> {
+    pub fn new(limits: &'a FilterLimits) -> Self {
+        FilterBudget {
+            limits,
+            conditions: 0,
+        }
+    }
+
+    // Called on entering a (nested) filter at `depth`
+    pub fn enter(&self, depth: usize) -> Result<(), FilterLimitError> {
+        if depth > self.limits.max_depth {
+            return Err(FilterLimitError::Depth(self.limits.max_depth));
+        }
+        Ok(())
+    }
 
-            // --- Fallback ---
-            _ => {
-                query = query.bind::<Option<String>>(None);
+    // Counts `count` more conditions toward the total
+    pub fn add_conditions(&mut self, count: usize) -> Result<(), FilterLimitError> {
+        self.conditions += count;
+        if self.conditions > self.limits.max_conditions {
+            return Err(FilterLimitError::Conditions(self.limits.max_conditions));
+        }
+        Ok(())
+    }
+
+    pub fn check_list(&self, len: usize) -> Result<(), FilterLimitError> {
+        if len > self.limits.max_list_length {
+            return Err(FilterLimitError::ListLength(self.limits.max_list_length));
+        }
+        Ok(())
+    }
+}
+
+// `column <operator> $n`
+pub fn compare(column: &str, operator: &str, param: impl Into<SqlParam>) -> Clause {
+    let mut clause = Clause::raw(&format!("{} {} ", column, operator));
+    clause.push_bind(param);
+    clause
+}
+
+// `column <operator> $n`, or `LOWER(column) <operator> LOWER($n)` when
+// comparing case-insensitively (the expression the LOWER(...) indexes in
+// init.sql are built on)
+pub fn compare_case(
+    column: &str,
+    operator: &str,
+    param: impl Into<SqlParam>,
+    insensitive: bool,
+) -> Clause {
+    if !insensitive {
+        return compare(column, operator, param);
+    }
+    let mut clause = Clause::raw(&format!("LOWER({}) {} LOWER(", column, operator));
+    clause.push_bind(param).push(")");
+    clause
+}
+
+// `column = ANY($n)` / `column <> ALL($n)` against a bound array, lowercasing
+// the column and every element when comparing case-insensitively
+pub fn list_condition(
+    column: &str,
+    operator: &str,
+    values: impl Into<SqlParam>,
+    insensitive: bool,
+) -> Clause {
+    let mut clause = if insensitive {
+        let mut clause = Clause::raw(&format!(
+            "LOWER({}) {}(SELECT LOWER(v) FROM unnest(",
+            column, operator
+        ));
+        clause.push_bind(values).push(") AS v");
+        clause
+    } else {
+        let mut clause = Clause::raw(&format!("{} {}(", column, operator));
+        clause.push_bind(values);
+        clause
+    };
+    clause.push(")");
+    clause
+}
+
+// `[NOT] EXISTS (SELECT 1 FROM table WHERE link AND (clause))`,
+// a correlated subquery for relation filters
+pub fn exists_subquery(table: &str, link: &str, clause: Clause, negated: bool) -> Clause {
+    let keyword = if negated { "NOT EXISTS" } else { "EXISTS" };
+    let mut subquery = Clause::raw(&format!(
+        "{} (SELECT 1 FROM {} WHERE {}",
+        keyword, table, link
+    ));
+    if !clause.is_empty() {
+        subquery.push(" AND ").push_clause(clause.parenthesized());
+    }
+    subquery.push(")");
+    subquery
+}
+
+// Escapes LIKE metacharacters (`%`, `_` and the `\` escape itself)
+// so user input matches literally inside a generated pattern.
+pub fn escape_like(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for c in value.chars() {
+        if matches!(c, '%' | '_' | '\\') {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out
+}
+
+// `column LIKE $n` (or ILIKE) with an explicit escape character,
+// matching the patterns produced with `escape_like`.
+pub fn like_condition(column: &str, operator: &str, pattern: String, insensitive: bool) -> Clause {
+    let mut clause = compare_case(column, operator, pattern, insensitive);
+    clause.push(" ESCAPE '\\'");
+    clause
+}
+
+// Full-text match of `column` against a websearch query.
+// The tsvector expression matches the GIN index in init.sql
+// (`to_tsvector('english', content)`), so Postgres can use it.
+pub fn text_search_condition(column: &str, query: String) -> Clause {
+    let mut clause = Clause::raw(&format!(
+        "to_tsvector('english', {}) @@ websearch_to_tsquery('english', ",
+        column
+    ));
+    clause.push_bind(query).push(")");
+    clause
+}
+
+// pg_trgm.similarity_threshold on every pooled connection (see
+// connect_options). `%` only matches above it, so it can prefilter any
+// similarTo threshold at or above it through the trigram indexes.
+pub const TRIGRAM_PREFILTER: f64 = 0.1;
+
+// similarTo threshold when none is given, pg_trgm's own default
+pub const DEFAULT_SIMILARITY_THRESHOLD: f64 = 0.3;
+
+// `column % $n AND similarity(column, $n) >= $m`. The `%` lets Postgres use
+// the gin_trgm_ops indexes in init.sql and similarity() applies the exact
+// threshold. Thresholds below TRIGRAM_PREFILTER would lose rows to the `%`,
+// so they get the similarity() check alone.
+pub fn similarity_condition(column: &str, value: String, threshold: f64) -> Clause {
+    let mut clause = Clause::new();
+    if threshold >= TRIGRAM_PREFILTER {
+        clause
+            .push_clause(compare(column, "%", value.clone()))
+            .push(" AND ");
+    }
+    clause
+        .push(&format!("similarity({}, ", column))
+        .push_bind(value)
+        .push(") >= ")
+        .push_bind(threshold);
+    clause
+}
+
+// Connection options for DATABASE_URL with the trigram prefilter threshold
+// set at connection startup, so it costs no extra statement per query
+pub fn connect_options(database_url: &str) -> Result<PgConnectOptions, sqlx::Error> {
+    Ok(database_url.parse::<PgConnectOptions>()?.options([(
+        "pg_trgm.similarity_threshold",
+        TRIGRAM_PREFILTER.to_string(),
+    )]))
+}
+
+// One column of an ORDER BY, as produced by the OrderBuilder derive
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SortKey {
+    pub column: String,
+    pub descending: bool,
+    // Explicit NULLS FIRST / LAST; Postgres puts nulls last for ASC, first for DESC
+    pub nulls_first: Option<bool>,
+    // False for the primary key, which keyset conditions then never test for NULL
+    pub nullable: bool,
+}
+
+impl SortKey {
+    pub fn sql(&self) -> String {
+        let direction = if self.descending { "DESC" } else { "ASC" };
+        match self.nulls_first {
+            Some(true) => format!("{} {} NULLS FIRST", self.column, direction),
+            Some(false) => format!("{} {} NULLS LAST", self.column, direction),
+            None => format!("{} {}", self.column, direction),
+        }
+    }
+
+    fn sorts_nulls_first(&self) -> bool {
+        self.nulls_first.unwrap_or(self.descending)
+    }
+
+    // The same column walked in the opposite direction
+    pub fn reversed(&self) -> SortKey {
+        SortKey {
+            column: self.column.clone(),
+            descending: !self.descending,
+            nulls_first: Some(!self.sorts_nulls_first()),
+            nullable: self.nullable,
+        }
+    }
+
+    // `column` holds `value`
+    fn equal_to(&self, value: &CursorValue) -> Clause {
+        match value.param() {
+            Some(param) => compare(&self.column, "=", param),
+            None => Clause::raw(&format!("{} IS NULL", self.column)),
+        }
+    }
+
+    // `column` sorts strictly after `value`; None when nothing can
+    fn after(&self, value: &CursorValue) -> Option<Clause> {
+        match value.param() {
+            Some(param) => {
+                let operator = if self.descending { "<" } else { ">" };
+                let cond = compare(&self.column, operator, param);
+                if self.sorts_nulls_first() || !self.nullable {
+                    Some(cond)
+                } else {
+                    let mut either = cond;
+                    either.push(&format!(" OR {} IS NULL", self.column));
+                    Some(either.parenthesized())
+                }
             }
+            None if self.sorts_nulls_first() => {
+                Some(Clause::raw(&format!("{} IS NOT NULL", self.column)))
+            }
+            None => None,
         }
     }
+}
+
+pub fn order_clause(keys: &[SortKey]) -> String {
+    let terms: Vec<String> = keys.iter().map(SortKey::sql).collect();
+    format!(" ORDER BY {}", terms.join(", "))
+}
+
+// Rows a cursor can be made for; implemented by `#[order(row = "...")]`
+pub trait Keyset {
+    // The row's value in a sortable column, None for columns it can't be
+    // ordered by
+    fn sort_value(&self, column: &str) -> Option<CursorValue>;
+
+    // Whether a client's cursor `value` for `column` has the type that
+    // column's row field would produce
+    fn accepts_sort_value(column: &str, value: &CursorValue) -> bool
+    where
+        Self: Sized;
+}
+
+// A sort column's value in a pagination cursor
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum CursorValue {
+    Null,
+    Int(i64),
+    Text(String),
+    DateTime(DateTime<Utc>),
+}
+
+impl CursorValue {
+    fn param(&self) -> Option<SqlParam> {
+        match self {
+            CursorValue::Null => None,
+            CursorValue::Int(value) => Some(SqlParam::BigInt(*value)),
+            CursorValue::Text(value) => Some(SqlParam::Text(value.clone())),
+            CursorValue::DateTime(value) => Some(SqlParam::DateTime(*value)),
+        }
+    }
+}
+
+impl From<i32> for CursorValue {
+    fn from(value: i32) -> Self {
+        CursorValue::Int(value.into())
+    }
+}
+
+impl From<Option<i32>> for CursorValue {
+    fn from(value: Option<i32>) -> Self {
+        value.map_or(CursorValue::Null, CursorValue::from)
+    }
+}
+
+impl From<Option<String>> for CursorValue {
+    fn from(value: Option<String>) -> Self {
+        value.map_or(CursorValue::Null, CursorValue::Text)
+    }
+}
+
+impl From<Option<DateTime<Utc>>> for CursorValue {
+    fn from(value: Option<DateTime<Utc>>) -> Self {
+        value.map_or(CursorValue::Null, CursorValue::DateTime)
+    }
+}
+
+// Row field types cursor values are read from, by the cursor values they
+// take back
+pub trait CursorField {
+    fn accepts(value: &CursorValue) -> bool;
+}
+
+impl CursorField for i32 {
+    fn accepts(value: &CursorValue) -> bool {
+        matches!(value, CursorValue::Int(_))
+    }
+}
+
+impl CursorField for Option<i32> {
+    fn accepts(value: &CursorValue) -> bool {
+        matches!(value, CursorValue::Int(_) | CursorValue::Null)
+    }
+}
+
+impl CursorField for Option<String> {
+    fn accepts(value: &CursorValue) -> bool {
+        matches!(value, CursorValue::Text(_) | CursorValue::Null)
+    }
+}
+
+impl CursorField for Option<DateTime<Utc>> {
+    fn accepts(value: &CursorValue) -> bool {
+        matches!(value, CursorValue::DateTime(_) | CursorValue::Null)
+    }
+}
+
+// Whether `value` fits the type of the row field `field` reads; the field is
+// only used for its type
+pub fn cursor_accepts<R, T: CursorField>(_field: fn(&R) -> &T, value: &CursorValue) -> bool {
+    T::accepts(value)
+}
+
+// Position of a row in a keyset-paginated list: its value for each sort
+// column, in sort order
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct KeysetCursor(pub Vec<(String, CursorValue)>);
+
+impl KeysetCursor {
+    // Values for `keys`, or None when the cursor was made under another sort
+    pub fn values_for(&self, keys: &[SortKey]) -> Option<Vec<CursorValue>> {
+        let same_columns = self.0.len() == keys.len()
+            && self
+                .0
+                .iter()
+                .zip(keys)
+                .all(|((column, _), key)| *column == key.column);
+        same_columns.then(|| self.0.iter().map(|(_, value)| value.clone()).collect())
+    }
+}
+
+// Rows strictly after the cursor `values` in `keys` order: for some key, all
+// earlier keys equal the cursor's and that key sorts past it
+pub fn keyset_condition(keys: &[SortKey], values: &[CursorValue]) -> Clause {
+    let mut alternatives = Vec::new();
+    for (i, (key, value)) in keys.iter().zip(values).enumerate() {
+        let Some(after) = key.after(value) else {
+            continue;
+        };
+        let mut conds: Vec<Clause> = keys[..i]
+            .iter()
+            .zip(values)
+            .map(|(k, v)| k.equal_to(v))
+            .collect();
+        conds.push(after);
+        alternatives.push(if conds.len() > 1 {
+            Clause::join(conds, " AND ").parenthesized()
+        } else {
+            Clause::join(conds, " AND ")
+        });
+    }
+    match alternatives.len() {
+        0 => Clause::raw("FALSE"),
+        1 => alternatives.remove(0),
+        _ => Clause::join(alternatives, " OR ").parenthesized(),
+    }
+}
+
+// One rejected input field, named as in the schema; produced by the
+// Validate derive and reported in VALIDATION_FAILED errors
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldError {
+    pub field: String,
+    pub message: String,
+    // Name and value of the limit the field exceeded, if that's the reason
+    pub limit: Option<(&'static str, usize)>,
+}
+
+impl FieldError {
+    pub fn new(field: &str, message: impl Into<String>) -> Self {
+        FieldError {
+            field: field.to_string(),
+            message: message.into(),
+            limit: None,
+        }
+    }
+}
+
+// A filter over its limits is a validation error on the `filters` argument
+impl From<FilterLimitError> for FieldError {
+    fn from(err: FilterLimitError) -> Self {
+        FieldError {
+            limit: Some((err.limit_name(), err.limit())),
+            ..FieldError::new("filters", err.to_string())
+        }
+    }
+}
+
+// Opaque, schema-wide ID of an object (`Node.id`): base64 of
+// "<TypeName>:<id>"
+#[derive(Debug, Clone, PartialEq)]
+pub struct GlobalId {
+    pub type_name: String,
+    pub id: i32,
+}
+
+impl GlobalId {
+    pub fn new(type_name: &str, id: i32) -> Self {
+        GlobalId {
+            type_name: type_name.to_string(),
+            id,
+        }
+    }
+
+    pub fn encode(&self) -> String {
+        STANDARD.encode(format!("{}:{}", self.type_name, self.id))
+    }
+
+    // None for anything `encode` could not have produced
+    pub fn decode(global_id: &str) -> Option<Self> {
+        let bytes = STANDARD.decode(global_id).ok()?;
+        let text = String::from_utf8(bytes).ok()?;
+        let (type_name, id) = text.split_once(':')?;
+        Some(GlobalId::new(type_name, id.parse().ok()?))
+    }
+}
+
+// Binds typed filter parameters to a SQLx query,
+// using exactly the Rust type each parameter was declared with.
+pub fn bind_dynamic_params<'q, T>(
+    mut query: QueryAs<'q, Postgres, T, PgArguments>,
+    params: Vec<SqlParam>,
+) -> QueryAs<'q, Postgres, T, PgArguments> {
+    for p in params {
+        query = match p {
+            SqlParam::Int(i) => query.bind(i),
+            SqlParam::BigInt(i) => query.bind(i),
+            SqlParam::Float(f) => query.bind(f),
+            SqlParam::Text(s) => query.bind(s),
+            SqlParam::Bool(b) => query.bind(b),
+            SqlParam::DateTime(dt) => query.bind(dt),
+            SqlParam::IntArray(v) => query.bind(v),
+            SqlParam::BigIntArray(v) => query.bind(v),
+            SqlParam::FloatArray(v) => query.bind(v),
+            SqlParam::TextArray(v) => query.bind(v),
+            SqlParam::BoolArray(v) => query.bind(v),
+            SqlParam::DateTimeArray(v) => query.bind(v),
+        };
+    }
 
     query
 }
@@ -0,0 +1,222 @@
+// This file contains GraphQL integration tests for single-object lookups and the Node interface.
+
+// Include utils module at crate root so resolvers can use crate::utils
+mod utils {
+    include!("../src/utils.rs");
+}
+
+// Include resolvers module directly at crate root
+// Now crate::utils in resolvers.rs will resolve to our utils module above
+mod resolvers {
+    include!("../src/resolvers.rs");
+}
+
+// Include test utilities
+mod test_utils {
+    include!("../src/test_utils.rs");
+}
+
+use test_utils::{build_schema, seed_test_data, setup};
+use utils::GlobalId;
+
+#[tokio::test]
+async fn test_user_and_post_lookup() {
+    // Test user(id:) and post(id:) return the object, or null when missing
+    let pool = setup().await;
+    seed_test_data(&pool)
+        .await
+        .expect("Failed to seed test data");
+    let schema = build_schema(
+        pool,
+        resolvers::Query::default(),
+        resolvers::Mutation::default(),
+    );
+    let query = r#"
+        query {
+            user(id: 2) {
+                databaseId
+                name
+            }
+            post(id: 1) {
+                databaseId
+                user {
+                    databaseId
+                }
+            }
+            missingUser: user(id: 999) {
+                databaseId
+            }
+            missingPost: post(id: 999) {
+                databaseId
+            }
+        }
+    "#;
+    let result = schema.execute(query).await;
+    assert!(result.errors.is_empty(), "{:?}", result.errors);
+    let data = result.data.into_json().unwrap();
+    assert_eq!(data["user"]["databaseId"], 2);
+    assert!(data["user"]["name"].is_string());
+    assert_eq!(data["post"]["databaseId"], 1);
+    assert_eq!(data["post"]["user"]["databaseId"], 1);
+    assert!(data["missingUser"].is_null());
+    assert!(data["missingPost"].is_null());
+}
+
+#[tokio::test]
+async fn test_node_refetches_by_node_id() {
+    // Test an object's global id resolves back to the same object through node(id:)
+    let pool = setup().await;
+    seed_test_data(&pool)
+        .await
+        .expect("Failed to seed test data");
+    let schema = build_schema(
+        pool,
+        resolvers::Query::default(),
+        resolvers::Mutation::default(),
+    );
+    let result = schema
+        .execute("query { user(id: 1) { id } post(id: 3) { id } }")
+        .await;
+    assert!(result.errors.is_empty(), "{:?}", result.errors);
+    let data = result.data.into_json().unwrap();
+    let user_id = data["user"]["id"].as_str().unwrap().to_string();
+    let post_id = data["post"]["id"].as_str().unwrap().to_string();
+    assert_eq!(GlobalId::decode(&user_id), Some(GlobalId::new("User", 1)));
+    assert_eq!(GlobalId::decode(&post_id), Some(GlobalId::new("Post", 3)));
+
+    let query = format!(
+        r#"
+        query {{
+            user: node(id: "{user_id}") {{
+                __typename
+                id
+                ... on User {{ databaseId name }}
+            }}
+            post: node(id: "{post_id}") {{
+                __typename
+                ... on Post {{ databaseId title }}
+            }}
+        }}
+    "#
+    );
+    let result = schema.execute(query.as_str()).await;
+    assert!(result.errors.is_empty(), "{:?}", result.errors);
+    let data = result.data.into_json().unwrap();
+    assert_eq!(data["user"]["__typename"], "User");
+    assert_eq!(data["user"]["id"], user_id.as_str());
+    assert_eq!(data["user"]["databaseId"], 1);
+    assert_eq!(data["post"]["__typename"], "Post");
+    assert_eq!(data["post"]["databaseId"], 3);
+    assert!(data["post"]["title"].is_string());
+}
+
+#[tokio::test]
+async fn test_nodes_keep_order_and_null_missing() {
+    // Test nodes(ids:) answers in request order with null for missing objects
+    let pool = setup().await;
+    seed_test_data(&pool)
+        .await
+        .expect("Failed to seed test data");
+    let schema = build_schema(
+        pool,
+        resolvers::Query::default(),
+        resolvers::Mutation::default(),
+    );
+    let ids = [
+        GlobalId::new("Post", 2),
+        GlobalId::new("User", 999),
+        GlobalId::new("User", 3),
+        GlobalId::new("Post", 1),
+    ]
+    .iter()
+    .map(|id| format!("\"{}\"", id.encode()))
+    .collect::<Vec<_>>()
+    .join(", ");
+    let query = format!(
+        r#"
+        query {{
+            nodes(ids: [{ids}]) {{
+                __typename
+                ... on User {{ databaseId }}
+                ... on Post {{ databaseId }}
+            }}
+        }}
+    "#
+    );
+    let result = schema.execute(query.as_str()).await;
+    assert!(result.errors.is_empty(), "{:?}", result.errors);
+    let data = result.data.into_json().unwrap();
+    let nodes: Vec<Option<(String, i64)>> = data["nodes"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|n| {
+            (!n.is_null()).then(|| {
+                (
+                    n["__typename"].as_str().unwrap().to_string(),
+                    n["databaseId"].as_i64().unwrap(),
+                )
+            })
+        })
+        .collect();
+    assert_eq!(
+        nodes,
+        vec![
+            Some(("Post".to_string(), 2)),
+            None,
+            Some(("User".to_string(), 3)),
+            Some(("Post".to_string(), 1)),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_node_rejects_malformed_id() {
+    // Test IDs that aren't global IDs of a known type are errors, not nulls
+    let pool = setup().await;
+    let schema = build_schema(
+        pool,
+        resolvers::Query::default(),
+        resolvers::Mutation::default(),
+    );
+    let unknown_type = GlobalId::new("Comment", 1).encode();
+    for id in ["not-base64!", "MQ==", unknown_type.as_str()] {
+        let query = format!(r#"query {{ node(id: "{id}") {{ id }} }}"#);
+        let result = schema.execute(query.as_str()).await;
+        assert_eq!(result.errors.len(), 1, "{id}");
+        assert!(result.errors[0].message.starts_with("Invalid node ID"));
+    }
+}
+
+#[tokio::test]
+async fn test_nodes_null_malformed_ids() {
+    // Test nodes(ids:) answers null for malformed IDs without failing the others
+    let pool = setup().await;
+    seed_test_data(&pool)
+        .await
+        .expect("Failed to seed test data");
+    let schema = build_schema(
+        pool,
+        resolvers::Query::default(),
+        resolvers::Mutation::default(),
+    );
+    let user = GlobalId::new("User", 2).encode();
+    let post = GlobalId::new("Post", 1).encode();
+    let unknown_type = GlobalId::new("Comment", 1).encode();
+    let query = format!(
+        r#"
+        query {{
+            nodes(ids: ["{user}", "not-base64!", "{post}", "{unknown_type}", "MQ=="]) {{
+                id
+            }}
+        }}
+    "#
+    );
+    let result = schema.execute(query.as_str()).await;
+    assert!(result.errors.is_empty(), "{:?}", result.errors);
+    let data = result.data.into_json().unwrap();
+    assert_eq!(
+        data["nodes"],
+        serde_json::json!([{ "id": user }, null, { "id": post }, null, null])
+    );
+}
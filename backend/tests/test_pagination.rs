@@ -16,6 +16,7 @@ mod test_utils {
     include!("../src/test_utils.rs");
 }
 
+use async_graphql::connection::{CursorType, OpaqueCursor};
 use test_utils::{build_schema, seed_test_data, setup};
 
 #[tokio::test]
@@ -34,7 +35,7 @@ async fn test_users_query_with_limit_zero() {
         query {
             users(limit: 0) {
                 data {
-                    id
+                    databaseId
                 }
                 totalCount
             }
@@ -64,7 +65,7 @@ async fn test_users_query_with_limit_exceeds_max() {
         query {
             users(limit: 500) {
                 data {
-                    id
+                    databaseId
                 }
                 totalCount
             }
@@ -94,7 +95,7 @@ async fn test_users_query_with_offset_beyond_total() {
         query {
             users(limit: 10, offset: 100) {
                 data {
-                    id
+                    databaseId
                 }
                 totalCount
             }
@@ -124,7 +125,7 @@ async fn test_posts_query_with_limit_zero() {
         query {
             posts(limit: 0) {
                 data {
-                    id
+                    databaseId
                 }
                 totalCount
             }
@@ -154,7 +155,7 @@ async fn test_posts_query_with_limit_exceeds_max() {
         query {
             posts(limit: 500) {
                 data {
-                    id
+                    databaseId
                 }
                 totalCount
             }
@@ -184,7 +185,7 @@ async fn test_posts_query_with_offset_beyond_total() {
         query {
             posts(limit: 10, offset: 100) {
                 data {
-                    id
+                    databaseId
                 }
                 totalCount
             }
@@ -197,3 +198,247 @@ async fn test_posts_query_with_offset_beyond_total() {
     assert_eq!(posts.len(), 0);
     assert_eq!(data["posts"]["totalCount"].as_i64().unwrap(), 3);
 }
+
+// ----- CURSOR CONNECTION TESTS -----
+
+// Ids on one page of a connection, plus its pageInfo and totalCount
+async fn connection_page(
+    schema: &async_graphql::Schema<
+        resolvers::Query,
+        resolvers::Mutation,
+        async_graphql::EmptySubscription,
+    >,
+    field: &str,
+    args: &str,
+) -> (Vec<i64>, serde_json::Value) {
+    let query = format!(
+        "query {{ {}({}) {{ edges {{ cursor node {{ databaseId }} }} \
+         pageInfo {{ hasNextPage hasPreviousPage startCursor endCursor }} totalCount }} }}",
+        field, args
+    );
+    let result = schema.execute(query.as_str()).await;
+    assert!(result.errors.is_empty(), "{:?}", result.errors);
+    let data = result.data.into_json().unwrap();
+    let connection = data[field].clone();
+    let ids = connection["edges"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|edge| edge["node"]["databaseId"].as_i64().unwrap())
+        .collect();
+    (ids, connection)
+}
+
+#[tokio::test]
+async fn test_users_connection_pages_forward_and_backward() {
+    // Test first/after and last/before walk the same order from either end
+    let pool = setup().await;
+    seed_test_data(&pool)
+        .await
+        .expect("Failed to seed test data");
+    let schema = build_schema(
+        pool,
+        resolvers::Query::default(),
+        resolvers::Mutation::default(),
+    );
+
+    let (ids, page) = connection_page(&schema, "usersConnection", "first: 2").await;
+    assert_eq!(ids, vec![1, 2]);
+    assert_eq!(page["totalCount"].as_i64().unwrap(), 3);
+    assert!(page["pageInfo"]["hasNextPage"].as_bool().unwrap());
+    assert!(!page["pageInfo"]["hasPreviousPage"].as_bool().unwrap());
+
+    let end = page["pageInfo"]["endCursor"].as_str().unwrap();
+    let args = format!("first: 2, after: \"{}\"", end);
+    let (ids, page) = connection_page(&schema, "usersConnection", &args).await;
+    assert_eq!(ids, vec![3]);
+    assert!(!page["pageInfo"]["hasNextPage"].as_bool().unwrap());
+    assert!(page["pageInfo"]["hasPreviousPage"].as_bool().unwrap());
+
+    let (ids, page) = connection_page(&schema, "usersConnection", "last: 2").await;
+    assert_eq!(ids, vec![2, 3]);
+    assert!(page["pageInfo"]["hasPreviousPage"].as_bool().unwrap());
+
+    let start = page["pageInfo"]["startCursor"].as_str().unwrap();
+    let args = format!("last: 2, before: \"{}\"", start);
+    let (ids, page) = connection_page(&schema, "usersConnection", &args).await;
+    assert_eq!(ids, vec![1]);
+    assert!(!page["pageInfo"]["hasPreviousPage"].as_bool().unwrap());
+    assert!(page["pageInfo"]["hasNextPage"].as_bool().unwrap());
+}
+
+#[tokio::test]
+async fn test_posts_connection_is_stable_across_inserts() {
+    // Test a post created between page loads does not shift the next page
+    let pool = setup().await;
+    seed_test_data(&pool)
+        .await
+        .expect("Failed to seed test data");
+    sqlx::query("UPDATE posts SET created_at = NOW() - (id || ' hours')::interval")
+        .execute(&pool)
+        .await
+        .expect("Failed to update posts");
+    let schema = build_schema(
+        pool.clone(),
+        resolvers::Query::default(),
+        resolvers::Mutation::default(),
+    );
+
+    // Newest first: post 1 was created an hour ago, post 3 three hours ago
+    let (ids, page) = connection_page(&schema, "postsConnection", "first: 2").await;
+    assert_eq!(ids, vec![1, 2]);
+
+    sqlx::query("INSERT INTO posts (user_id, title, content) VALUES (1, 'Newest', 'Fresh')")
+        .execute(&pool)
+        .await
+        .expect("Failed to insert post");
+
+    let end = page["pageInfo"]["endCursor"].as_str().unwrap();
+    let args = format!("first: 2, after: \"{}\"", end);
+    let (ids, page) = connection_page(&schema, "postsConnection", &args).await;
+    assert_eq!(ids, vec![3]);
+    assert_eq!(page["totalCount"].as_i64().unwrap(), 4);
+}
+
+#[tokio::test]
+async fn test_users_connection_keyset_on_nullable_sort_key() {
+    // Test paging one row at a time through a DESC sort with a null age
+    let pool = setup().await;
+    seed_test_data(&pool)
+        .await
+        .expect("Failed to seed test data");
+    sqlx::query("UPDATE users SET age = NULL WHERE id = 2")
+        .execute(&pool)
+        .await
+        .expect("Failed to update user");
+    let schema = build_schema(
+        pool,
+        resolvers::Query::default(),
+        resolvers::Mutation::default(),
+    );
+
+    let order_by = "orderBy: [{ age: { direction: DESC } }], first: 1";
+    let mut seen = Vec::new();
+    let mut args = order_by.to_string();
+    loop {
+        let (ids, page) = connection_page(&schema, "usersConnection", &args).await;
+        seen.extend(ids);
+        if !page["pageInfo"]["hasNextPage"].as_bool().unwrap() {
+            break;
+        }
+        let end = page["pageInfo"]["endCursor"].as_str().unwrap();
+        args = format!("{}, after: \"{}\"", order_by, end);
+    }
+    // DESC puts nulls first
+    assert_eq!(seen, vec![2, 3, 1]);
+}
+
+#[tokio::test]
+async fn test_posts_connection_orders_by_every_sort_column() {
+    // Test each PostOrderBy column, deletedAt included, can build and follow cursors
+    let pool = setup().await;
+    seed_test_data(&pool)
+        .await
+        .expect("Failed to seed test data");
+    let schema = build_schema(
+        pool,
+        resolvers::Query::default(),
+        resolvers::Mutation::default(),
+    );
+
+    for column in [
+        "id",
+        "userId",
+        "title",
+        "content",
+        "createdAt",
+        "updatedAt",
+        "deletedAt",
+    ] {
+        let order_by = format!(
+            "orderBy: [{{ {}: {{ direction: DESC }} }}], first: 1",
+            column
+        );
+        let mut seen = Vec::new();
+        let mut args = order_by.clone();
+        loop {
+            let (ids, page) = connection_page(&schema, "postsConnection", &args).await;
+            seen.extend(ids);
+            if !page["pageInfo"]["hasNextPage"].as_bool().unwrap() {
+                break;
+            }
+            let end = page["pageInfo"]["endCursor"].as_str().unwrap();
+            args = format!("{}, after: \"{}\"", order_by, end);
+        }
+        seen.sort_unstable();
+        assert_eq!(seen, vec![1, 2, 3], "{column}");
+    }
+}
+
+#[tokio::test]
+async fn test_connection_rejects_cursor_from_other_order() {
+    // Test a cursor issued under one orderBy is refused under another
+    let pool = setup().await;
+    seed_test_data(&pool)
+        .await
+        .expect("Failed to seed test data");
+    let schema = build_schema(
+        pool,
+        resolvers::Query::default(),
+        resolvers::Mutation::default(),
+    );
+
+    let (_, page) = connection_page(&schema, "usersConnection", "first: 1").await;
+    let end = page["pageInfo"]["endCursor"].as_str().unwrap();
+    let query = format!(
+        "query {{ usersConnection(orderBy: [{{ name: {{}} }}], after: \"{}\") {{ totalCount }} }}",
+        end
+    );
+    let result = schema.execute(query.as_str()).await;
+    assert_eq!(result.errors.len(), 1);
+    assert_eq!(
+        result.errors[0].message,
+        "Cursor does not match the current orderBy"
+    );
+}
+
+#[tokio::test]
+async fn test_connection_rejects_cursor_with_wrong_value_type() {
+    // Test a tampered cursor holding text for the integer id is a validation
+    // error instead of a database one
+    let pool = setup().await;
+    seed_test_data(&pool)
+        .await
+        .expect("Failed to seed test data");
+    let schema = build_schema(
+        pool,
+        resolvers::Query::default(),
+        resolvers::Mutation::default(),
+    );
+    let cursor = OpaqueCursor(utils::KeysetCursor(vec![(
+        "id".to_string(),
+        utils::CursorValue::Text("1 OR 1=1".to_string()),
+    )]))
+    .encode_cursor();
+    for arg in ["after", "before"] {
+        let query = format!(
+            "query {{ usersConnection(first: 1, {}: \"{}\") {{ totalCount }} }}",
+            arg, cursor
+        );
+        let result = schema.execute(query.as_str()).await;
+        assert_eq!(result.errors.len(), 1);
+        assert_eq!(
+            result.errors[0].message,
+            "Cursor has an invalid value for id"
+        );
+        let extensions = result.errors[0].extensions.as_ref().unwrap();
+        assert_eq!(extensions.get("code"), Some(&"VALIDATION_FAILED".into()));
+        assert_eq!(
+            extensions.get("fields").unwrap(),
+            &async_graphql::value!([{
+                "field": arg,
+                "message": "Cursor has an invalid value for id"
+            }])
+        );
+    }
+}
@@ -0,0 +1,412 @@
+// This file contains GraphQL integration tests for the bulk "posts" mutations.
+
+// Include utils module at crate root so resolvers can use crate::utils
+mod utils {
+    include!("../src/utils.rs");
+}
+
+// Include resolvers module directly at crate root
+// Now crate::utils in resolvers.rs will resolve to our utils module above
+mod resolvers {
+    include!("../src/resolvers.rs");
+}
+
+// Include test utilities
+mod test_utils {
+    include!("../src/test_utils.rs");
+}
+
+use sqlx::PgPool;
+use test_utils::{build_schema, error_extension, seed_test_data, setup};
+
+fn ids_of(value: &serde_json::Value) -> Vec<i64> {
+    value["ids"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|id| id.as_i64().unwrap())
+        .collect()
+}
+
+async fn post_count(pool: &PgPool) -> i64 {
+    sqlx::query_scalar("SELECT COUNT(*) FROM posts")
+        .fetch_one(pool)
+        .await
+        .unwrap()
+}
+
+// ----- createPosts -----
+
+#[tokio::test]
+async fn test_create_posts_inserts_all() {
+    // Test every input becomes a post, with trimmed titles and empty content stored as NULL
+    let pool = setup().await;
+    seed_test_data(&pool)
+        .await
+        .expect("Failed to seed test data");
+    let schema = build_schema(
+        pool.clone(),
+        resolvers::Query::default(),
+        resolvers::Mutation::default(),
+    );
+    let mutation = r#"
+        mutation {
+            createPosts(inputs: [
+                { userId: 1, title: "  Imported 1  ", content: "Body" }
+                { userId: 2, title: "Imported 2", content: "" }
+                { userId: 3, title: "Imported 3" }
+            ]) {
+                ids
+                count
+                dryRun
+            }
+        }
+    "#;
+    let result = schema.execute(mutation).await;
+    assert!(result.errors.is_empty(), "{:?}", result.errors);
+    let data = result.data.into_json().unwrap();
+    let created = &data["createPosts"];
+    assert_eq!(created["count"], 3);
+    assert_eq!(created["dryRun"], false);
+    let ids = ids_of(created);
+    assert_eq!(ids.len(), 3);
+    assert_eq!(post_count(&pool).await, 6);
+
+    let rows: Vec<(i32, String, Option<String>)> =
+        sqlx::query_as("SELECT user_id, title, content FROM posts WHERE id = ANY($1) ORDER BY id")
+            .bind(ids.iter().map(|&id| id as i32).collect::<Vec<_>>())
+            .fetch_all(&pool)
+            .await
+            .unwrap();
+    assert_eq!(
+        rows,
+        vec![
+            (1, "Imported 1".to_string(), Some("Body".to_string())),
+            (2, "Imported 2".to_string(), None),
+            (3, "Imported 3".to_string(), None),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_create_posts_invalid_input_inserts_nothing() {
+    // Test one invalid input fails the whole batch and names the input by index
+    let pool = setup().await;
+    seed_test_data(&pool)
+        .await
+        .expect("Failed to seed test data");
+    let schema = build_schema(
+        pool.clone(),
+        resolvers::Query::default(),
+        resolvers::Mutation::default(),
+    );
+    let mutation = r#"
+        mutation {
+            createPosts(inputs: [
+                { userId: 1, title: "Fine" }
+                { userId: 1, title: "   " }
+            ]) {
+                count
+            }
+        }
+    "#;
+    let result = schema.execute(mutation).await;
+    assert_eq!(result.errors.len(), 1);
+    assert_eq!(error_extension(&result, "code"), "VALIDATION_FAILED".into());
+    let fields = error_extension(&result, "fields").into_json().unwrap();
+    assert_eq!(fields[0]["field"], "inputs.1.title");
+    assert_eq!(post_count(&pool).await, 3);
+}
+
+#[tokio::test]
+async fn test_create_posts_unknown_user_is_foreign_key() {
+    // Test a missing author reports FOREIGN_KEY for its input and inserts nothing
+    let pool = setup().await;
+    seed_test_data(&pool)
+        .await
+        .expect("Failed to seed test data");
+    let schema = build_schema(
+        pool.clone(),
+        resolvers::Query::default(),
+        resolvers::Mutation::default(),
+    );
+    let mutation = r#"
+        mutation {
+            createPosts(inputs: [
+                { userId: 1, title: "Fine" }
+                { userId: 999, title: "Orphan" }
+            ]) {
+                count
+            }
+        }
+    "#;
+    let result = schema.execute(mutation).await;
+    assert_eq!(result.errors.len(), 1);
+    assert_eq!(error_extension(&result, "code"), "FOREIGN_KEY".into());
+    assert_eq!(error_extension(&result, "field"), "inputs.1.userId".into());
+    assert!(result.errors[0].message.contains("User 999 does not exist"));
+    assert_eq!(post_count(&pool).await, 3);
+}
+
+#[tokio::test]
+async fn test_create_posts_empty_list() {
+    // Test an empty batch is a no-op
+    let pool = setup().await;
+    seed_test_data(&pool)
+        .await
+        .expect("Failed to seed test data");
+    let schema = build_schema(
+        pool.clone(),
+        resolvers::Query::default(),
+        resolvers::Mutation::default(),
+    );
+    let result = schema
+        .execute("mutation { createPosts(inputs: []) { ids count } }")
+        .await;
+    assert!(result.errors.is_empty(), "{:?}", result.errors);
+    let data = result.data.into_json().unwrap();
+    assert_eq!(data["createPosts"]["count"], 0);
+    assert_eq!(post_count(&pool).await, 3);
+}
+
+// ----- updatePosts -----
+
+#[tokio::test]
+async fn test_update_posts_by_filter() {
+    // Test only the posts matching the filter get the new values
+    let pool = setup().await;
+    seed_test_data(&pool)
+        .await
+        .expect("Failed to seed test data");
+    let schema = build_schema(
+        pool.clone(),
+        resolvers::Query::default(),
+        resolvers::Mutation::default(),
+    );
+    let mutation = r#"
+        mutation {
+            updatePosts(
+                filters: { userId: { equals: 1 } }
+                set: { title: " Archived ", userId: 3 }
+            ) {
+                ids
+                count
+                dryRun
+            }
+        }
+    "#;
+    let result = schema.execute(mutation).await;
+    assert!(result.errors.is_empty(), "{:?}", result.errors);
+    let data = result.data.into_json().unwrap();
+    assert_eq!(ids_of(&data["updatePosts"]), vec![1, 2]);
+    assert_eq!(data["updatePosts"]["count"], 2);
+    assert_eq!(data["updatePosts"]["dryRun"], false);
+
+    let rows: Vec<(i32, i32, String)> =
+        sqlx::query_as("SELECT id, user_id, title FROM posts ORDER BY id")
+            .fetch_all(&pool)
+            .await
+            .unwrap();
+    assert_eq!(
+        rows,
+        vec![
+            (1, 3, "Archived".to_string()),
+            (2, 3, "Archived".to_string()),
+            (3, 2, "Test Post 3".to_string()),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_update_posts_dry_run_changes_nothing() {
+    // Test dryRun reports the matching posts without updating them
+    let pool = setup().await;
+    seed_test_data(&pool)
+        .await
+        .expect("Failed to seed test data");
+    let schema = build_schema(
+        pool.clone(),
+        resolvers::Query::default(),
+        resolvers::Mutation::default(),
+    );
+    let mutation = r#"
+        mutation {
+            updatePosts(
+                filters: { title: { endsWith: "3" } }
+                set: { content: "" }
+                dryRun: true
+            ) {
+                ids
+                count
+                dryRun
+            }
+        }
+    "#;
+    let result = schema.execute(mutation).await;
+    assert!(result.errors.is_empty(), "{:?}", result.errors);
+    let data = result.data.into_json().unwrap();
+    assert_eq!(ids_of(&data["updatePosts"]), vec![3]);
+    assert_eq!(data["updatePosts"]["dryRun"], true);
+
+    let content: Option<String> = sqlx::query_scalar("SELECT content FROM posts WHERE id = 3")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(content.as_deref(), Some("Content for post 3"));
+}
+
+#[tokio::test]
+async fn test_update_posts_unknown_user_is_foreign_key() {
+    // Test moving posts to a missing user reports FOREIGN_KEY, dry run or not
+    let pool = setup().await;
+    seed_test_data(&pool)
+        .await
+        .expect("Failed to seed test data");
+    let schema = build_schema(
+        pool,
+        resolvers::Query::default(),
+        resolvers::Mutation::default(),
+    );
+    for dry_run in [false, true] {
+        let mutation = format!(
+            r#"
+            mutation {{
+                updatePosts(
+                    filters: {{ id: {{ equals: 1 }} }}
+                    set: {{ userId: 999 }}
+                    dryRun: {dry_run}
+                ) {{
+                    count
+                }}
+            }}
+        "#
+        );
+        let result = schema.execute(mutation.as_str()).await;
+        assert_eq!(result.errors.len(), 1, "dryRun: {dry_run}");
+        assert_eq!(error_extension(&result, "code"), "FOREIGN_KEY".into());
+        assert_eq!(error_extension(&result, "field"), "userId".into());
+        assert!(result.errors[0].message.contains("User 999 does not exist"));
+    }
+}
+
+#[tokio::test]
+async fn test_update_posts_requires_changes() {
+    // Test an empty set is rejected before anything runs
+    let pool = setup().await;
+    seed_test_data(&pool)
+        .await
+        .expect("Failed to seed test data");
+    let schema = build_schema(
+        pool,
+        resolvers::Query::default(),
+        resolvers::Mutation::default(),
+    );
+    let mutation = r#"
+        mutation {
+            updatePosts(filters: { id: { equals: 1 } }, set: {}) {
+                count
+            }
+        }
+    "#;
+    let result = schema.execute(mutation).await;
+    assert_eq!(result.errors.len(), 1);
+    assert_eq!(error_extension(&result, "code"), "VALIDATION_FAILED".into());
+    assert!(result.errors[0].message.contains("Nothing to update"));
+}
+
+// ----- deletePosts -----
+
+#[tokio::test]
+async fn test_delete_posts_by_filter() {
+    // Test only the posts matching the filter move to the trash, including relation filters
+    let pool = setup().await;
+    seed_test_data(&pool)
+        .await
+        .expect("Failed to seed test data");
+    let schema = build_schema(
+        pool.clone(),
+        resolvers::Query::default(),
+        resolvers::Mutation::default(),
+    );
+    let mutation = r#"
+        mutation {
+            deletePosts(filters: { user: { id: { equals: 1 } } }) {
+                ids
+                count
+                dryRun
+            }
+        }
+    "#;
+    let result = schema.execute(mutation).await;
+    assert!(result.errors.is_empty(), "{:?}", result.errors);
+    let data = result.data.into_json().unwrap();
+    assert_eq!(ids_of(&data["deletePosts"]), vec![1, 2]);
+    assert_eq!(data["deletePosts"]["count"], 2);
+
+    let trashed: Vec<(i32, bool)> =
+        sqlx::query_as("SELECT id, deleted_at IS NOT NULL FROM posts ORDER BY id")
+            .fetch_all(&pool)
+            .await
+            .unwrap();
+    assert_eq!(trashed, vec![(1, true), (2, true), (3, false)]);
+
+    // Trashed posts are out of reach of further bulk deletes
+    let result = schema.execute(mutation).await;
+    assert!(result.errors.is_empty(), "{:?}", result.errors);
+    let data = result.data.into_json().unwrap();
+    assert_eq!(data["deletePosts"]["count"], 0);
+}
+
+#[tokio::test]
+async fn test_delete_posts_dry_run_keeps_posts() {
+    // Test dryRun reports the matching posts without deleting them
+    let pool = setup().await;
+    seed_test_data(&pool)
+        .await
+        .expect("Failed to seed test data");
+    let schema = build_schema(
+        pool.clone(),
+        resolvers::Query::default(),
+        resolvers::Mutation::default(),
+    );
+    let mutation = r#"
+        mutation {
+            deletePosts(filters: { id: { in: [1, 3, 999] } }, dryRun: true) {
+                ids
+                count
+                dryRun
+            }
+        }
+    "#;
+    let result = schema.execute(mutation).await;
+    assert!(result.errors.is_empty(), "{:?}", result.errors);
+    let data = result.data.into_json().unwrap();
+    assert_eq!(ids_of(&data["deletePosts"]), vec![1, 3]);
+    assert_eq!(data["deletePosts"]["dryRun"], true);
+    assert_eq!(post_count(&pool).await, 3);
+}
+
+#[tokio::test]
+async fn test_bulk_mutations_reject_empty_filter() {
+    // Test a filter without conditions is refused instead of touching every post
+    let pool = setup().await;
+    seed_test_data(&pool)
+        .await
+        .expect("Failed to seed test data");
+    let schema = build_schema(
+        pool.clone(),
+        resolvers::Query::default(),
+        resolvers::Mutation::default(),
+    );
+    for mutation in [
+        "mutation { deletePosts(filters: {}) { count } }",
+        r#"mutation { updatePosts(filters: {}, set: { title: "x" }) { count } }"#,
+    ] {
+        let result = schema.execute(mutation).await;
+        assert_eq!(result.errors.len(), 1, "{mutation}");
+        assert_eq!(error_extension(&result, "code"), "VALIDATION_FAILED".into());
+        let fields = error_extension(&result, "fields").into_json().unwrap();
+        assert_eq!(fields[0]["field"], "filters");
+    }
+    assert_eq!(post_count(&pool).await, 3);
+}
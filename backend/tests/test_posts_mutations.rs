@@ -16,7 +16,7 @@ mod test_utils {
     include!("../src/test_utils.rs");
 }
 
-use test_utils::{build_schema, seed_test_data, setup};
+use test_utils::{build_schema, error_extension, seed_test_data, setup};
 
 // ----- POST MUTATION TESTS -----
 
@@ -39,7 +39,7 @@ async fn test_create_post_mutation() {
                 title: "New Test Post"
                 content: "This is test content"
             }) {
-                id
+                databaseId
                 title
                 content
             }
@@ -75,7 +75,7 @@ async fn test_create_post_mutation_empty_title() {
                 title: "   "
                 content: "Test content"
             }) {
-                id
+                databaseId
                 title
             }
         }
@@ -104,7 +104,7 @@ async fn test_create_post_mutation_with_empty_content_string() {
                 title: "Post with empty content"
                 content: "   "
             }) {
-                id
+                databaseId
                 title
                 content
             }
@@ -136,7 +136,7 @@ async fn test_create_post_mutation_with_null_content() {
                 userId: 1
                 title: "Post without content"
             }) {
-                id
+                databaseId
                 title
                 content
             }
@@ -168,7 +168,7 @@ async fn test_update_post_mutation() {
                 id: 1
                 title: "Updated Title"
             }) {
-                id
+                databaseId
                 title
                 content
             }
@@ -198,7 +198,7 @@ async fn test_update_post_mutation_empty_fields() {
             updatePost(input: {
                 id: 1
             }) {
-                id
+                databaseId
             }
         }
     "#;
@@ -225,7 +225,7 @@ async fn test_update_post_mutation_with_only_content() {
                 id: 1
                 content: "Updated content only"
             }) {
-                id
+                databaseId
                 title
                 content
             }
@@ -235,7 +235,7 @@ async fn test_update_post_mutation_with_only_content() {
     assert!(result.errors.is_empty());
     let data = result.data.into_json().unwrap();
     let post = &data["updatePost"];
-    assert_eq!(post["id"].as_i64().unwrap(), 1);
+    assert_eq!(post["databaseId"].as_i64().unwrap(), 1);
     assert_eq!(post["title"].as_str().unwrap(), "Test Post 1");
     assert_eq!(post["content"].as_str().unwrap(), "Updated content only");
 }
@@ -258,7 +258,7 @@ async fn test_update_post_mutation_with_empty_content_string() {
                 id: 1
                 content: "   "
             }) {
-                id
+                databaseId
                 title
                 content
             }
@@ -268,7 +268,7 @@ async fn test_update_post_mutation_with_empty_content_string() {
     assert!(result.errors.is_empty());
     let data = result.data.into_json().unwrap();
     let post = &data["updatePost"];
-    assert_eq!(post["id"].as_i64().unwrap(), 1);
+    assert_eq!(post["databaseId"].as_i64().unwrap(), 1);
     assert!(post["content"].is_null());
 }
 
@@ -292,13 +292,13 @@ async fn test_delete_post_mutation() {
     let result = schema.execute(mutation).await;
     assert!(result.errors.is_empty());
     let data = result.data.into_json().unwrap();
-    assert_eq!(data["deletePost"].as_bool().unwrap(), true);
+    assert!(data["deletePost"].as_bool().unwrap());
     // Verify post was deleted
     let query = r#"
         query {
             posts(filters: { id: { equals: 1 } }) {
                 data {
-                    id
+                    databaseId
                 }
                 totalCount
             }
@@ -330,5 +330,149 @@ async fn test_delete_post_mutation_nonexistent() {
     let result = schema.execute(mutation).await;
     assert!(result.errors.is_empty());
     let data = result.data.into_json().unwrap();
-    assert_eq!(data["deletePost"].as_bool().unwrap(), false);
+    assert!(!data["deletePost"].as_bool().unwrap());
+}
+
+// ----- ERROR CODE TESTS -----
+
+#[tokio::test]
+async fn test_update_post_mutation_nonexistent_is_not_found() {
+    // Test updating a missing post reports NOT_FOUND instead of the raw sqlx error
+    let pool = setup().await;
+    seed_test_data(&pool)
+        .await
+        .expect("Failed to seed test data");
+    let schema = build_schema(
+        pool,
+        resolvers::Query::default(),
+        resolvers::Mutation::default(),
+    );
+    let mutation = r#"
+        mutation {
+            updatePost(input: { id: 999, title: "Nope" }) {
+                databaseId
+            }
+        }
+    "#;
+    let result = schema.execute(mutation).await;
+    assert_eq!(result.errors.len(), 1);
+    assert_eq!(result.errors[0].message, "Post 999 not found");
+    assert_eq!(error_extension(&result, "code"), "NOT_FOUND".into());
+}
+
+#[tokio::test]
+async fn test_create_post_mutation_unknown_user_is_foreign_key() {
+    // Test a post for a missing user reports FOREIGN_KEY without the Postgres message
+    let pool = setup().await;
+    seed_test_data(&pool)
+        .await
+        .expect("Failed to seed test data");
+    let schema = build_schema(
+        pool,
+        resolvers::Query::default(),
+        resolvers::Mutation::default(),
+    );
+    let mutation = r#"
+        mutation {
+            createPost(input: { userId: 999, title: "Orphan" }) {
+                databaseId
+            }
+        }
+    "#;
+    let result = schema.execute(mutation).await;
+    assert_eq!(result.errors.len(), 1);
+    assert_eq!(result.errors[0].message, "User 999 does not exist");
+    assert_eq!(error_extension(&result, "code"), "FOREIGN_KEY".into());
+    assert_eq!(error_extension(&result, "field"), "userId".into());
+}
+
+#[tokio::test]
+async fn test_post_validation_error_lists_fields() {
+    // Test validation failures carry VALIDATION_FAILED and the offending field
+    let pool = setup().await;
+    let schema = build_schema(
+        pool,
+        resolvers::Query::default(),
+        resolvers::Mutation::default(),
+    );
+    let mutation = r#"
+        mutation {
+            createPost(input: { userId: 1, title: "  " }) {
+                databaseId
+            }
+        }
+    "#;
+    let result = schema.execute(mutation).await;
+    assert_eq!(result.errors.len(), 1);
+    assert_eq!(error_extension(&result, "code"), "VALIDATION_FAILED".into());
+    assert_eq!(
+        error_extension(&result, "fields"),
+        async_graphql::value!([{ "field": "title", "message": "Title cannot be empty" }])
+    );
+}
+
+// ----- VALIDATION TESTS -----
+
+#[tokio::test]
+async fn test_update_post_mutation_rejects_blank_and_long_titles() {
+    // Test updatePost applies the same title rules as createPost
+    let pool = setup().await;
+    seed_test_data(&pool)
+        .await
+        .expect("Failed to seed test data");
+    let schema = build_schema(
+        pool,
+        resolvers::Query::default(),
+        resolvers::Mutation::default(),
+    );
+    let long_title = "x".repeat(51);
+    for (title, message) in [
+        ("   ", "Title cannot be empty"),
+        (
+            long_title.as_str(),
+            "Title cannot be longer than 50 characters",
+        ),
+    ] {
+        let mutation = format!(
+            r#"mutation {{ updatePost(input: {{ id: 1, title: "{title}" }}) {{ databaseId }} }}"#
+        );
+        let result = schema.execute(mutation.as_str()).await;
+        assert_eq!(result.errors.len(), 1, "{title}");
+        assert_eq!(error_extension(&result, "code"), "VALIDATION_FAILED".into());
+        assert_eq!(
+            error_extension(&result, "fields"),
+            async_graphql::value!([{ "field": "title", "message": message }])
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_post_mutations_trim_title_and_content() {
+    // Test titles and content are stored trimmed by both mutations
+    let pool = setup().await;
+    seed_test_data(&pool)
+        .await
+        .expect("Failed to seed test data");
+    let schema = build_schema(
+        pool,
+        resolvers::Query::default(),
+        resolvers::Mutation::default(),
+    );
+    let mutation = r#"
+        mutation {
+            created: createPost(input: { userId: 1, title: "  Spaced  ", content: " Body " }) {
+                title
+                content
+            }
+            updated: updatePost(input: { id: 1, title: "\tRenamed " }) {
+                title
+            }
+        }
+    "#;
+    let result = schema.execute(mutation).await;
+    assert!(result.errors.is_empty(), "{:?}", result.errors);
+    let data = result.data.into_json().unwrap();
+    assert_eq!(data["created"]["title"], "Spaced");
+    assert_eq!(data["created"]["content"], "Body");
+    assert_eq!(data["updated"]["title"], "Renamed");
 }
@@ -36,7 +36,7 @@ async fn test_posts_query_basic() {
         query {
             posts {
                 data {
-                    id
+                    databaseId
                     title
                     content
                 }
@@ -71,7 +71,7 @@ async fn test_posts_query_with_user_filter() {
         query {
             posts(filters: { userId: { equals: 1 } }) {
                 data {
-                    id
+                    databaseId
                     title
                     userId
                 }
@@ -108,7 +108,7 @@ async fn test_posts_query_with_empty_result() {
         query {
             posts(filters: { id: { equals: 999 } }) {
                 data {
-                    id
+                    databaseId
                 }
                 totalCount
             }
@@ -138,7 +138,7 @@ async fn test_posts_query_with_pagination_and_filter() {
         query {
             posts(filters: { userId: { equals: 1 } }, limit: 1, offset: 0) {
                 data {
-                    id
+                    databaseId
                     userId
                 }
                 totalCount
@@ -172,7 +172,7 @@ async fn test_posts_query_with_id_equals() {
         query {
             posts(filters: { id: { equals: 2 } }) {
                 data {
-                    id
+                    databaseId
                     title
                 }
                 totalCount
@@ -185,7 +185,7 @@ async fn test_posts_query_with_id_equals() {
     let posts = data["posts"]["data"].as_array().unwrap();
     assert_eq!(posts.len(), 1);
     assert_eq!(data["posts"]["totalCount"].as_i64().unwrap(), 1);
-    assert_eq!(posts[0]["id"].as_i64().unwrap(), 2);
+    assert_eq!(posts[0]["databaseId"].as_i64().unwrap(), 2);
 }
 
 #[tokio::test]
@@ -204,7 +204,7 @@ async fn test_posts_query_with_id_gt() {
         query {
             posts(filters: { id: { gt: 1 } }) {
                 data {
-                    id
+                    databaseId
                     title
                 }
                 totalCount
@@ -218,7 +218,7 @@ async fn test_posts_query_with_id_gt() {
     assert_eq!(posts.len(), 2);
     assert_eq!(data["posts"]["totalCount"].as_i64().unwrap(), 2);
     for post in posts {
-        assert!(post["id"].as_i64().unwrap() > 1);
+        assert!(post["databaseId"].as_i64().unwrap() > 1);
     }
 }
 
@@ -238,7 +238,7 @@ async fn test_posts_query_with_id_gte() {
         query {
             posts(filters: { id: { gte: 2 } }) {
                 data {
-                    id
+                    databaseId
                     title
                 }
                 totalCount
@@ -252,7 +252,7 @@ async fn test_posts_query_with_id_gte() {
     assert_eq!(posts.len(), 2);
     assert_eq!(data["posts"]["totalCount"].as_i64().unwrap(), 2);
     for post in posts {
-        assert!(post["id"].as_i64().unwrap() >= 2);
+        assert!(post["databaseId"].as_i64().unwrap() >= 2);
     }
 }
 
@@ -272,7 +272,7 @@ async fn test_posts_query_with_id_lt() {
         query {
             posts(filters: { id: { lt: 3 } }) {
                 data {
-                    id
+                    databaseId
                     title
                 }
                 totalCount
@@ -286,7 +286,7 @@ async fn test_posts_query_with_id_lt() {
     assert_eq!(posts.len(), 2);
     assert_eq!(data["posts"]["totalCount"].as_i64().unwrap(), 2);
     for post in posts {
-        assert!(post["id"].as_i64().unwrap() < 3);
+        assert!(post["databaseId"].as_i64().unwrap() < 3);
     }
 }
 
@@ -306,7 +306,7 @@ async fn test_posts_query_with_id_lte() {
         query {
             posts(filters: { id: { lte: 2 } }) {
                 data {
-                    id
+                    databaseId
                     title
                 }
                 totalCount
@@ -320,7 +320,7 @@ async fn test_posts_query_with_id_lte() {
     assert_eq!(posts.len(), 2);
     assert_eq!(data["posts"]["totalCount"].as_i64().unwrap(), 2);
     for post in posts {
-        assert!(post["id"].as_i64().unwrap() <= 2);
+        assert!(post["databaseId"].as_i64().unwrap() <= 2);
     }
 }
 
@@ -340,7 +340,7 @@ async fn test_posts_query_with_id_not_equals() {
         query {
             posts(filters: { or: [ { id: { lt: 2 } }, { id: { gt: 2 } } ] }) {
                 data {
-                    id
+                    databaseId
                     title
                 }
                 totalCount
@@ -354,7 +354,7 @@ async fn test_posts_query_with_id_not_equals() {
     assert_eq!(posts.len(), 2);
     assert_eq!(data["posts"]["totalCount"].as_i64().unwrap(), 2);
     for post in posts {
-        assert_ne!(post["id"].as_i64().unwrap(), 2);
+        assert_ne!(post["databaseId"].as_i64().unwrap(), 2);
     }
 }
 
@@ -374,7 +374,7 @@ async fn test_posts_query_with_user_id_equals() {
         query {
             posts(filters: { userId: { equals: 1 } }) {
                 data {
-                    id
+                    databaseId
                     userId
                 }
                 totalCount
@@ -408,7 +408,7 @@ async fn test_posts_query_with_user_id_gt() {
         query {
             posts(filters: { userId: { gt: 1 } }) {
                 data {
-                    id
+                    databaseId
                     userId
                 }
                 totalCount
@@ -440,7 +440,7 @@ async fn test_posts_query_with_user_id_gte() {
         query {
             posts(filters: { userId: { gte: 2 } }) {
                 data {
-                    id
+                    databaseId
                     userId
                 }
                 totalCount
@@ -472,7 +472,7 @@ async fn test_posts_query_with_user_id_lt() {
         query {
             posts(filters: { userId: { lt: 2 } }) {
                 data {
-                    id
+                    databaseId
                     userId
                 }
                 totalCount
@@ -506,7 +506,7 @@ async fn test_posts_query_with_user_id_lte() {
         query {
             posts(filters: { userId: { lte: 1 } }) {
                 data {
-                    id
+                    databaseId
                     userId
                 }
                 totalCount
@@ -540,7 +540,7 @@ async fn test_posts_query_with_user_id_not_equals() {
         query {
             posts(filters: { or: [ { userId: { lt: 2 } }, { userId: { gt: 2 } } ] }) {
                 data {
-                    id
+                    databaseId
                     userId
                 }
                 totalCount
@@ -558,6 +558,72 @@ async fn test_posts_query_with_user_id_not_equals() {
     }
 }
 
+#[tokio::test]
+async fn test_posts_query_with_user_id_in() {
+    // Test filtering posts by a set of user IDs
+    let pool = setup().await;
+    seed_test_data(&pool)
+        .await
+        .expect("Failed to seed test data");
+    let schema = build_schema(
+        pool,
+        resolvers::Query::default(),
+        resolvers::Mutation::default(),
+    );
+    let query = r#"
+        query {
+            posts(filters: { userId: { in: [2, 3] } }) {
+                data {
+                    databaseId
+                    userId
+                }
+                totalCount
+            }
+        }
+    "#;
+    let result = schema.execute(query).await;
+    assert!(result.errors.is_empty());
+    let data = result.data.into_json().unwrap();
+    let posts = data["posts"]["data"].as_array().unwrap();
+    assert_eq!(posts.len(), 1);
+    assert_eq!(data["posts"]["totalCount"].as_i64().unwrap(), 1);
+    assert_eq!(posts[0]["userId"].as_i64().unwrap(), 2);
+}
+
+#[tokio::test]
+async fn test_posts_query_with_user_id_not_in() {
+    // Test excluding posts by a set of user IDs
+    let pool = setup().await;
+    seed_test_data(&pool)
+        .await
+        .expect("Failed to seed test data");
+    let schema = build_schema(
+        pool,
+        resolvers::Query::default(),
+        resolvers::Mutation::default(),
+    );
+    let query = r#"
+        query {
+            posts(filters: { userId: { notIn: [2] } }) {
+                data {
+                    databaseId
+                    userId
+                }
+                totalCount
+            }
+        }
+    "#;
+    let result = schema.execute(query).await;
+    assert!(result.errors.is_empty());
+    let data = result.data.into_json().unwrap();
+    let posts = data["posts"]["data"].as_array().unwrap();
+    assert_eq!(posts.len(), 2);
+    assert_eq!(data["posts"]["totalCount"].as_i64().unwrap(), 2);
+    for post in posts {
+        assert_eq!(post["userId"].as_i64().unwrap(), 1);
+    }
+}
+
 // ----- POST QUERY TESTS - STRINGFILTER OPERATORS -----
 
 #[tokio::test]
@@ -576,7 +642,7 @@ async fn test_posts_query_with_title_equals() {
         query {
             posts(filters: { title: { equals: "Test Post 1" } }) {
                 data {
-                    id
+                    databaseId
                     title
                 }
                 totalCount
@@ -608,7 +674,7 @@ async fn test_posts_query_with_title_contains() {
         query {
             posts(filters: { title: { contains: "Post 1" } }) {
                 data {
-                    id
+                    databaseId
                     title
                 }
                 totalCount
@@ -640,7 +706,7 @@ async fn test_posts_query_with_title_starts_with() {
         query {
             posts(filters: { title: { startsWith: "Test" } }) {
                 data {
-                    id
+                    databaseId
                     title
                 }
                 totalCount
@@ -674,7 +740,7 @@ async fn test_posts_query_with_title_ends_with() {
         query {
             posts(filters: { title: { endsWith: "Post 2" } }) {
                 data {
-                    id
+                    databaseId
                     title
                 }
                 totalCount
@@ -706,7 +772,7 @@ async fn test_posts_query_with_title_contains_insensitive() {
         query {
             posts(filters: { title: { containsInsensitive: "POST 1" } }) {
                 data {
-                    id
+                    databaseId
                     title
                 }
                 totalCount
@@ -722,6 +788,42 @@ async fn test_posts_query_with_title_contains_insensitive() {
     assert_eq!(posts[0]["title"].as_str().unwrap(), "Test Post 1");
 }
 
+#[tokio::test]
+async fn test_posts_query_with_date_like_title() {
+    // Test a title that looks like a timestamp is matched as plain text
+    let pool = setup().await;
+    seed_test_data(&pool)
+        .await
+        .expect("Failed to seed test data");
+    sqlx::query("INSERT INTO posts (user_id, title) VALUES (1, '2025-01-01T00:00:00Z')")
+        .execute(&pool)
+        .await
+        .expect("Failed to insert post");
+    let schema = build_schema(
+        pool,
+        resolvers::Query::default(),
+        resolvers::Mutation::default(),
+    );
+    let query = r#"
+        query {
+            posts(filters: { title: { equals: "2025-01-01T00:00:00Z" } }) {
+                data {
+                    databaseId
+                    title
+                }
+                totalCount
+            }
+        }
+    "#;
+    let result = schema.execute(query).await;
+    assert!(result.errors.is_empty());
+    let data = result.data.into_json().unwrap();
+    let posts = data["posts"]["data"].as_array().unwrap();
+    assert_eq!(posts.len(), 1);
+    assert_eq!(data["posts"]["totalCount"].as_i64().unwrap(), 1);
+    assert_eq!(posts[0]["title"].as_str().unwrap(), "2025-01-01T00:00:00Z");
+}
+
 #[tokio::test]
 async fn test_posts_query_with_content_equals() {
     // Test filtering posts by exact content match
@@ -738,7 +840,7 @@ async fn test_posts_query_with_content_equals() {
         query {
             posts(filters: { content: { equals: "Content for post 1" } }) {
                 data {
-                    id
+                    databaseId
                     content
                 }
                 totalCount
@@ -770,7 +872,7 @@ async fn test_posts_query_with_content_contains() {
         query {
             posts(filters: { content: { contains: "post 1" } }) {
                 data {
-                    id
+                    databaseId
                     content
                 }
                 totalCount
@@ -802,7 +904,7 @@ async fn test_posts_query_with_content_starts_with() {
         query {
             posts(filters: { content: { startsWith: "Content" } }) {
                 data {
-                    id
+                    databaseId
                     content
                 }
                 totalCount
@@ -836,7 +938,7 @@ async fn test_posts_query_with_content_ends_with() {
         query {
             posts(filters: { content: { endsWith: "post 1" } }) {
                 data {
-                    id
+                    databaseId
                     content
                 }
                 totalCount
@@ -868,7 +970,7 @@ async fn test_posts_query_with_content_contains_insensitive() {
         query {
             posts(filters: { content: { containsInsensitive: "POST 2" } }) {
                 data {
-                    id
+                    databaseId
                     content
                 }
                 totalCount
@@ -886,6 +988,215 @@ async fn test_posts_query_with_content_contains_insensitive() {
 
 // ----- POST QUERY TESTS - LOGICAL OPERATORS -----
 
+#[tokio::test]
+async fn test_posts_query_with_created_at_range() {
+    // Test filtering posts by a created_at window
+    let pool = setup().await;
+    seed_test_data(&pool)
+        .await
+        .expect("Failed to seed test data");
+    sqlx::query("UPDATE posts SET created_at = '2025-01-01T10:00:00Z' WHERE id = 1")
+        .execute(&pool)
+        .await
+        .expect("Failed to update post");
+    sqlx::query("UPDATE posts SET created_at = '2025-01-05T10:00:00Z' WHERE id = 2")
+        .execute(&pool)
+        .await
+        .expect("Failed to update post");
+    let schema = build_schema(
+        pool,
+        resolvers::Query::default(),
+        resolvers::Mutation::default(),
+    );
+    let query = r#"
+        query {
+            posts(filters: {
+                createdAt: {
+                    between: { from: "2025-01-01T00:00:00Z", to: "2025-01-03T00:00:00Z" }
+                }
+            }) {
+                data {
+                    databaseId
+                }
+                totalCount
+            }
+        }
+    "#;
+    let result = schema.execute(query).await;
+    assert!(result.errors.is_empty());
+    let data = result.data.into_json().unwrap();
+    let posts = data["posts"]["data"].as_array().unwrap();
+    assert_eq!(posts.len(), 1);
+    assert_eq!(data["posts"]["totalCount"].as_i64().unwrap(), 1);
+    assert_eq!(posts[0]["databaseId"].as_i64().unwrap(), 1);
+}
+
+#[tokio::test]
+async fn test_posts_query_with_created_at_before() {
+    // Test filtering posts created before a given instant
+    let pool = setup().await;
+    seed_test_data(&pool)
+        .await
+        .expect("Failed to seed test data");
+    sqlx::query("UPDATE posts SET created_at = '2025-01-01T10:00:00Z' WHERE id IN (1, 2)")
+        .execute(&pool)
+        .await
+        .expect("Failed to update posts");
+    let schema = build_schema(
+        pool,
+        resolvers::Query::default(),
+        resolvers::Mutation::default(),
+    );
+    let query = r#"
+        query {
+            posts(filters: { createdAt: { before: "2025-06-01T00:00:00Z" } }) {
+                data {
+                    databaseId
+                }
+                totalCount
+            }
+        }
+    "#;
+    let result = schema.execute(query).await;
+    assert!(result.errors.is_empty());
+    let data = result.data.into_json().unwrap();
+    assert_eq!(data["posts"]["totalCount"].as_i64().unwrap(), 2);
+}
+
+#[tokio::test]
+async fn test_posts_query_with_content_is_null() {
+    // Test filtering posts that have no content
+    let pool = setup().await;
+    seed_test_data(&pool)
+        .await
+        .expect("Failed to seed test data");
+    sqlx::query("INSERT INTO posts (user_id, title, content) VALUES (3, 'Draft', NULL)")
+        .execute(&pool)
+        .await
+        .expect("Failed to insert post");
+    let schema = build_schema(
+        pool,
+        resolvers::Query::default(),
+        resolvers::Mutation::default(),
+    );
+    let query = r#"
+        query {
+            posts(filters: { content: { isNull: true } }) {
+                data {
+                    databaseId
+                    title
+                    content
+                }
+                totalCount
+            }
+        }
+    "#;
+    let result = schema.execute(query).await;
+    assert!(result.errors.is_empty());
+    let data = result.data.into_json().unwrap();
+    let posts = data["posts"]["data"].as_array().unwrap();
+    assert_eq!(posts.len(), 1);
+    assert_eq!(data["posts"]["totalCount"].as_i64().unwrap(), 1);
+    assert_eq!(posts[0]["title"].as_str().unwrap(), "Draft");
+    assert!(posts[0]["content"].is_null());
+}
+
+#[tokio::test]
+async fn test_posts_query_with_content_is_not_null() {
+    // Test filtering posts that have content
+    let pool = setup().await;
+    seed_test_data(&pool)
+        .await
+        .expect("Failed to seed test data");
+    sqlx::query("INSERT INTO posts (user_id, title, content) VALUES (3, 'Draft', NULL)")
+        .execute(&pool)
+        .await
+        .expect("Failed to insert post");
+    let schema = build_schema(
+        pool,
+        resolvers::Query::default(),
+        resolvers::Mutation::default(),
+    );
+    let query = r#"
+        query {
+            posts(filters: { content: { isNull: false } }) {
+                data {
+                    databaseId
+                }
+                totalCount
+            }
+        }
+    "#;
+    let result = schema.execute(query).await;
+    assert!(result.errors.is_empty());
+    let data = result.data.into_json().unwrap();
+    assert_eq!(data["posts"]["totalCount"].as_i64().unwrap(), 3);
+}
+
+#[tokio::test]
+async fn test_posts_query_with_not_filter() {
+    // Test excluding posts whose title contains a substring
+    let pool = setup().await;
+    seed_test_data(&pool)
+        .await
+        .expect("Failed to seed test data");
+    let schema = build_schema(
+        pool,
+        resolvers::Query::default(),
+        resolvers::Mutation::default(),
+    );
+    let query = r#"
+        query {
+            posts(filters: { not: { title: { contains: "Post 1" } } }) {
+                data {
+                    databaseId
+                    title
+                }
+                totalCount
+            }
+        }
+    "#;
+    let result = schema.execute(query).await;
+    assert!(result.errors.is_empty());
+    let data = result.data.into_json().unwrap();
+    let posts = data["posts"]["data"].as_array().unwrap();
+    assert_eq!(posts.len(), 2);
+    assert_eq!(data["posts"]["totalCount"].as_i64().unwrap(), 2);
+    for post in posts {
+        assert!(!post["title"].as_str().unwrap().contains("Post 1"));
+    }
+}
+
+#[tokio::test]
+async fn test_posts_query_with_and_filter() {
+    // Test combining post conditions with AND
+    let pool = setup().await;
+    seed_test_data(&pool)
+        .await
+        .expect("Failed to seed test data");
+    let schema = build_schema(
+        pool,
+        resolvers::Query::default(),
+        resolvers::Mutation::default(),
+    );
+    let query = r#"
+        query {
+            posts(filters: { and: [ { userId: { equals: 1 } }, { title: { endsWith: "2" } } ] }) {
+                data {
+                    databaseId
+                }
+                totalCount
+            }
+        }
+    "#;
+    let result = schema.execute(query).await;
+    assert!(result.errors.is_empty());
+    let data = result.data.into_json().unwrap();
+    let posts = data["posts"]["data"].as_array().unwrap();
+    assert_eq!(posts.len(), 1);
+    assert_eq!(posts[0]["databaseId"].as_i64().unwrap(), 2);
+}
+
 #[tokio::test]
 async fn test_posts_query_with_or_filter() {
     // Test filtering posts using OR logic with nested filters
@@ -905,7 +1216,7 @@ async fn test_posts_query_with_or_filter() {
                 { id: { equals: 3 } }
             ] }) {
                 data {
-                    id
+                    databaseId
                     title
                 }
                 totalCount
@@ -918,7 +1229,255 @@ async fn test_posts_query_with_or_filter() {
     let posts = data["posts"]["data"].as_array().unwrap();
     assert_eq!(posts.len(), 2);
     assert_eq!(data["posts"]["totalCount"].as_i64().unwrap(), 2);
-    let ids: Vec<i64> = posts.iter().map(|p| p["id"].as_i64().unwrap()).collect();
+    let ids: Vec<i64> = posts
+        .iter()
+        .map(|p| p["databaseId"].as_i64().unwrap())
+        .collect();
     assert!(ids.contains(&1));
     assert!(ids.contains(&3));
 }
+
+#[tokio::test]
+async fn test_posts_query_with_order_by_multiple_columns() {
+    // Test sorting posts by author, then title descending
+    let pool = setup().await;
+    seed_test_data(&pool)
+        .await
+        .expect("Failed to seed test data");
+    let schema = build_schema(
+        pool,
+        resolvers::Query::default(),
+        resolvers::Mutation::default(),
+    );
+    let query = r#"
+        query {
+            posts(orderBy: [{ userId: { direction: DESC } }, { title: { direction: DESC } }]) {
+                data {
+                    databaseId
+                }
+            }
+        }
+    "#;
+    let result = schema.execute(query).await;
+    assert!(result.errors.is_empty());
+    let data = result.data.into_json().unwrap();
+    let posts = data["posts"]["data"].as_array().unwrap();
+    let ids: Vec<i64> = posts
+        .iter()
+        .map(|p| p["databaseId"].as_i64().unwrap())
+        .collect();
+    assert_eq!(ids, vec![3, 2, 1]);
+}
+
+#[tokio::test]
+async fn test_posts_query_default_order_is_stable() {
+    // Test posts with identical createdAt fall back to id order
+    let pool = setup().await;
+    seed_test_data(&pool)
+        .await
+        .expect("Failed to seed test data");
+    let schema = build_schema(
+        pool,
+        resolvers::Query::default(),
+        resolvers::Mutation::default(),
+    );
+    let query = r#"
+        query {
+            posts(limit: 2, offset: 1) {
+                data {
+                    databaseId
+                }
+            }
+        }
+    "#;
+    let result = schema.execute(query).await;
+    assert!(result.errors.is_empty());
+    let data = result.data.into_json().unwrap();
+    let posts = data["posts"]["data"].as_array().unwrap();
+    let ids: Vec<i64> = posts
+        .iter()
+        .map(|p| p["databaseId"].as_i64().unwrap())
+        .collect();
+    assert_eq!(ids, vec![2, 3]);
+}
+
+#[tokio::test]
+async fn test_posts_query_with_content_search_ranks_results() {
+    // Test full-text search orders by relevance and returns highlighted excerpts
+    let pool = setup().await;
+    seed_test_data(&pool)
+        .await
+        .expect("Failed to seed test data");
+    sqlx::query(
+        "UPDATE posts SET content = CASE id \
+            WHEN 1 THEN 'An introduction to Rust' \
+            WHEN 2 THEN 'Rust ownership rules make Rust code safe' \
+            ELSE 'Cooking fresh pasta' END",
+    )
+    .execute(&pool)
+    .await
+    .expect("Failed to update posts");
+    let schema = build_schema(
+        pool,
+        resolvers::Query::default(),
+        resolvers::Mutation::default(),
+    );
+    let query = r#"
+        query {
+            posts(filters: { content: { search: "rust" } }) {
+                data {
+                    databaseId
+                    rank
+                    headline
+                }
+                totalCount
+            }
+        }
+    "#;
+    let result = schema.execute(query).await;
+    assert!(result.errors.is_empty());
+    let data = result.data.into_json().unwrap();
+    let posts = data["posts"]["data"].as_array().unwrap();
+    assert_eq!(data["posts"]["totalCount"].as_i64().unwrap(), 2);
+    let ids: Vec<i64> = posts
+        .iter()
+        .map(|p| p["databaseId"].as_i64().unwrap())
+        .collect();
+    assert_eq!(ids, vec![2, 1]);
+    assert!(posts[0]["rank"].as_f64().unwrap() > posts[1]["rank"].as_f64().unwrap());
+    assert!(
+        posts[1]["headline"]
+            .as_str()
+            .unwrap()
+            .contains("<b>Rust</b>")
+    );
+}
+
+#[tokio::test]
+async fn test_posts_query_with_nested_content_search_ranks_results() {
+    // Test the search still ranks and highlights when combined under and/or
+    let pool = setup().await;
+    seed_test_data(&pool)
+        .await
+        .expect("Failed to seed test data");
+    sqlx::query(
+        "UPDATE posts SET content = CASE id \
+            WHEN 1 THEN 'An introduction to Rust' \
+            WHEN 2 THEN 'Rust ownership rules make Rust code safe' \
+            ELSE 'Cooking fresh pasta' END",
+    )
+    .execute(&pool)
+    .await
+    .expect("Failed to update posts");
+    let schema = build_schema(
+        pool,
+        resolvers::Query::default(),
+        resolvers::Mutation::default(),
+    );
+    let query = r#"
+        query {
+            posts(filters: {
+                and: [
+                    { userId: { equals: 1 } }
+                    { or: [{ title: { equals: "none" } }, { content: { search: "rust" } }] }
+                ]
+            }) {
+                data {
+                    databaseId
+                    rank
+                    headline
+                }
+            }
+        }
+    "#;
+    let result = schema.execute(query).await;
+    assert!(result.errors.is_empty(), "{:?}", result.errors);
+    let data = result.data.into_json().unwrap();
+    let posts = data["posts"]["data"].as_array().unwrap();
+    let ids: Vec<i64> = posts
+        .iter()
+        .map(|p| p["databaseId"].as_i64().unwrap())
+        .collect();
+    assert_eq!(ids, vec![2, 1]);
+    assert!(posts[0]["rank"].as_f64().unwrap() > posts[1]["rank"].as_f64().unwrap());
+    assert!(
+        posts[1]["headline"]
+            .as_str()
+            .unwrap()
+            .contains("<b>Rust</b>")
+    );
+}
+
+#[tokio::test]
+async fn test_posts_query_without_search_has_no_rank() {
+    // Test rank and headline are null when the query has no content search
+    let pool = setup().await;
+    seed_test_data(&pool)
+        .await
+        .expect("Failed to seed test data");
+    let schema = build_schema(
+        pool,
+        resolvers::Query::default(),
+        resolvers::Mutation::default(),
+    );
+    let query = r#"
+        query {
+            posts(limit: 1) {
+                data {
+                    rank
+                    headline
+                }
+            }
+        }
+    "#;
+    let result = schema.execute(query).await;
+    assert!(result.errors.is_empty());
+    let data = result.data.into_json().unwrap();
+    let post = &data["posts"]["data"][0];
+    assert!(post["rank"].is_null());
+    assert!(post["headline"].is_null());
+}
+
+#[tokio::test]
+async fn test_posts_query_with_title_similar_to() {
+    // Test typo-tolerant title search is ordered by similarity
+    let pool = setup().await;
+    seed_test_data(&pool)
+        .await
+        .expect("Failed to seed test data");
+    sqlx::query(
+        "UPDATE posts SET title = CASE id \
+            WHEN 1 THEN 'Kubernetes Deployment' \
+            WHEN 2 THEN 'Kubernetes' \
+            ELSE 'Cooking Pasta' END",
+    )
+    .execute(&pool)
+    .await
+    .expect("Failed to update posts");
+    let schema = build_schema(
+        pool,
+        resolvers::Query::default(),
+        resolvers::Mutation::default(),
+    );
+    let query = r#"
+        query {
+            posts(filters: { title: { similarTo: { value: "Kubernetis" } } }) {
+                data {
+                    databaseId
+                    similarity
+                }
+                totalCount
+            }
+        }
+    "#;
+    let result = schema.execute(query).await;
+    assert!(result.errors.is_empty());
+    let data = result.data.into_json().unwrap();
+    let posts = data["posts"]["data"].as_array().unwrap();
+    let ids: Vec<i64> = posts
+        .iter()
+        .map(|p| p["databaseId"].as_i64().unwrap())
+        .collect();
+    assert_eq!(ids, vec![2, 1]);
+    assert_eq!(data["posts"]["totalCount"].as_i64().unwrap(), 2);
+}
@@ -0,0 +1,310 @@
+// This file contains GraphQL integration tests for soft-deleted posts: the trash,
+// restoring and purging.
+
+// Include utils module at crate root so resolvers can use crate::utils
+mod utils {
+    include!("../src/utils.rs");
+}
+
+// Include resolvers module directly at crate root
+// Now crate::utils in resolvers.rs will resolve to our utils module above
+mod resolvers {
+    include!("../src/resolvers.rs");
+}
+
+// Include test utilities
+mod test_utils {
+    include!("../src/test_utils.rs");
+}
+
+use sqlx::PgPool;
+use test_utils::{build_schema, error_extension, seed_test_data, setup};
+use utils::{GlobalId, TrashRetention};
+
+async fn trash_post(pool: &PgPool, id: i32, days_ago: i32) {
+    sqlx::query("UPDATE posts SET deleted_at = NOW() - make_interval(days => $2) WHERE id = $1")
+        .bind(id)
+        .bind(days_ago)
+        .execute(pool)
+        .await
+        .unwrap();
+}
+
+async fn post_ids(pool: &PgPool) -> Vec<i32> {
+    sqlx::query_scalar("SELECT id FROM posts ORDER BY id")
+        .fetch_all(pool)
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_delete_post_moves_it_to_trash() {
+    // Test deletePost keeps the row but hides it from every posts listing and count
+    let pool = setup().await;
+    seed_test_data(&pool)
+        .await
+        .expect("Failed to seed test data");
+    let schema = build_schema(
+        pool.clone(),
+        resolvers::Query::default(),
+        resolvers::Mutation::default(),
+    );
+    let result = schema.execute("mutation { deletePost(id: 1) }").await;
+    assert!(result.errors.is_empty(), "{:?}", result.errors);
+    assert_eq!(result.data.into_json().unwrap()["deletePost"], true);
+    assert_eq!(post_ids(&pool).await, vec![1, 2, 3]);
+
+    let query = r#"
+        query {
+            posts { totalCount data { databaseId } }
+            postsConnection(first: 10) { totalCount edges { node { databaseId } } }
+            user(id: 1) {
+                postsCount
+                posts { totalCount data { databaseId } }
+            }
+            trash { totalCount data { databaseId deletedAt } }
+        }
+    "#;
+    let result = schema.execute(query).await;
+    assert!(result.errors.is_empty(), "{:?}", result.errors);
+    let data = result.data.into_json().unwrap();
+    assert_eq!(data["posts"]["totalCount"], 2);
+    assert!(
+        data["posts"]["data"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .all(|p| p["databaseId"] != 1)
+    );
+    assert_eq!(data["postsConnection"]["totalCount"], 2);
+    assert_eq!(data["user"]["postsCount"], 1);
+    assert_eq!(data["user"]["posts"]["totalCount"], 1);
+    assert_eq!(data["user"]["posts"]["data"][0]["databaseId"], 2);
+    assert_eq!(data["trash"]["totalCount"], 1);
+    assert_eq!(data["trash"]["data"][0]["databaseId"], 1);
+    assert!(data["trash"]["data"][0]["deletedAt"].is_string());
+
+    // A post already in the trash can't be deleted again
+    let result = schema.execute("mutation { deletePost(id: 1) }").await;
+    assert_eq!(result.data.into_json().unwrap()["deletePost"], false);
+}
+
+#[tokio::test]
+async fn test_trash_newest_deleted_first() {
+    // Test the trash lists the most recently deleted posts first and takes filters
+    let pool = setup().await;
+    seed_test_data(&pool)
+        .await
+        .expect("Failed to seed test data");
+    trash_post(&pool, 1, 3).await;
+    trash_post(&pool, 3, 1).await;
+    let schema = build_schema(
+        pool,
+        resolvers::Query::default(),
+        resolvers::Mutation::default(),
+    );
+    let query = r#"
+        query {
+            trash { data { databaseId } }
+            byUser: trash(filters: { userId: { equals: 1 } }) { totalCount data { databaseId } }
+        }
+    "#;
+    let result = schema.execute(query).await;
+    assert!(result.errors.is_empty(), "{:?}", result.errors);
+    let data = result.data.into_json().unwrap();
+    let ids: Vec<i64> = data["trash"]["data"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|p| p["databaseId"].as_i64().unwrap())
+        .collect();
+    assert_eq!(ids, vec![3, 1]);
+    assert_eq!(data["byUser"]["totalCount"], 1);
+    assert_eq!(data["byUser"]["data"][0]["databaseId"], 1);
+}
+
+#[tokio::test]
+async fn test_restore_post() {
+    // Test restorePost brings a trashed post back and rejects live ones
+    let pool = setup().await;
+    seed_test_data(&pool)
+        .await
+        .expect("Failed to seed test data");
+    trash_post(&pool, 2, 0).await;
+    let schema = build_schema(
+        pool,
+        resolvers::Query::default(),
+        resolvers::Mutation::default(),
+    );
+    let result = schema
+        .execute("mutation { restorePost(id: 2) { databaseId deletedAt } }")
+        .await;
+    assert!(result.errors.is_empty(), "{:?}", result.errors);
+    let data = result.data.into_json().unwrap();
+    assert_eq!(data["restorePost"]["databaseId"], 2);
+    assert!(data["restorePost"]["deletedAt"].is_null());
+
+    let result = schema.execute("query { posts { totalCount } }").await;
+    assert_eq!(result.data.into_json().unwrap()["posts"]["totalCount"], 3);
+
+    let result = schema
+        .execute("mutation { restorePost(id: 2) { databaseId } }")
+        .await;
+    assert_eq!(result.errors.len(), 1);
+    assert_eq!(error_extension(&result, "code"), "NOT_FOUND".into());
+    assert!(
+        result.errors[0]
+            .message
+            .contains("Post 2 is not in the trash")
+    );
+}
+
+#[tokio::test]
+async fn test_purge_post_only_from_trash() {
+    // Test purgePost deletes trashed posts for good and leaves live ones alone
+    let pool = setup().await;
+    seed_test_data(&pool)
+        .await
+        .expect("Failed to seed test data");
+    trash_post(&pool, 1, 0).await;
+    let schema = build_schema(
+        pool.clone(),
+        resolvers::Query::default(),
+        resolvers::Mutation::default(),
+    );
+    let result = schema.execute("mutation { purgePost(id: 2) }").await;
+    assert!(result.errors.is_empty(), "{:?}", result.errors);
+    assert_eq!(result.data.into_json().unwrap()["purgePost"], false);
+
+    let result = schema.execute("mutation { purgePost(id: 1) }").await;
+    assert!(result.errors.is_empty(), "{:?}", result.errors);
+    assert_eq!(result.data.into_json().unwrap()["purgePost"], true);
+    assert_eq!(post_ids(&pool).await, vec![2, 3]);
+}
+
+#[tokio::test]
+async fn test_update_trashed_post_is_not_found() {
+    // Test trashed posts can't be edited until they are restored
+    let pool = setup().await;
+    seed_test_data(&pool)
+        .await
+        .expect("Failed to seed test data");
+    trash_post(&pool, 1, 0).await;
+    let schema = build_schema(
+        pool,
+        resolvers::Query::default(),
+        resolvers::Mutation::default(),
+    );
+    let result = schema
+        .execute(r#"mutation { updatePost(input: { id: 1, title: "Edited" }) { databaseId } }"#)
+        .await;
+    assert_eq!(result.errors.len(), 1);
+    assert_eq!(error_extension(&result, "code"), "NOT_FOUND".into());
+}
+
+#[tokio::test]
+async fn test_trashed_post_lookup_is_null() {
+    // Test post(id:), node(id:) and nodes(ids:) don't reach trashed posts
+    let pool = setup().await;
+    seed_test_data(&pool)
+        .await
+        .expect("Failed to seed test data");
+    trash_post(&pool, 1, 0).await;
+    let schema = build_schema(
+        pool,
+        resolvers::Query::default(),
+        resolvers::Mutation::default(),
+    );
+    let trashed = GlobalId::new("Post", 1).encode();
+    let live = GlobalId::new("Post", 2).encode();
+    let query = format!(
+        r#"
+        query {{
+            post(id: 1) {{ databaseId }}
+            node(id: "{trashed}") {{ id }}
+            nodes(ids: ["{trashed}", "{live}"]) {{ id }}
+        }}
+    "#
+    );
+    let result = schema.execute(query.as_str()).await;
+    assert!(result.errors.is_empty(), "{:?}", result.errors);
+    let data = result.data.into_json().unwrap();
+    assert!(data["post"].is_null());
+    assert!(data["node"].is_null());
+    assert_eq!(data["nodes"], serde_json::json!([null, { "id": live }]));
+}
+
+#[tokio::test]
+async fn test_relation_filters_skip_trashed_posts() {
+    // Test users are matched by their live posts only
+    let pool = setup().await;
+    seed_test_data(&pool)
+        .await
+        .expect("Failed to seed test data");
+    trash_post(&pool, 3, 0).await;
+    let schema = build_schema(
+        pool,
+        resolvers::Query::default(),
+        resolvers::Mutation::default(),
+    );
+    let query = r#"
+        query {
+            withPosts: users(filters: { posts: { some: {} } }) { data { databaseId } }
+            withoutPosts: users(filters: { posts: { none: {} } }) { data { databaseId } }
+        }
+    "#;
+    let result = schema.execute(query).await;
+    assert!(result.errors.is_empty(), "{:?}", result.errors);
+    let data = result.data.into_json().unwrap();
+    let ids = |key: &str| -> Vec<i64> {
+        data[key]["data"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|u| u["databaseId"].as_i64().unwrap())
+            .collect()
+    };
+    assert_eq!(ids("withPosts"), vec![1]);
+    assert_eq!(ids("withoutPosts"), vec![2, 3]);
+}
+
+#[tokio::test]
+async fn test_delete_user_ignores_trashed_posts() {
+    // Test REFUSE only counts live posts, and trashed ones go with the user
+    let pool = setup().await;
+    seed_test_data(&pool)
+        .await
+        .expect("Failed to seed test data");
+    trash_post(&pool, 3, 0).await;
+    let schema = build_schema(
+        pool.clone(),
+        resolvers::Query::default(),
+        resolvers::Mutation::default(),
+    );
+    let result = schema.execute("mutation { deleteUser(id: 2) }").await;
+    assert!(result.errors.is_empty(), "{:?}", result.errors);
+    assert_eq!(result.data.into_json().unwrap()["deleteUser"], true);
+    assert_eq!(post_ids(&pool).await, vec![1, 2]);
+}
+
+#[tokio::test]
+async fn test_purge_expired_posts_respects_retention() {
+    // Test only posts trashed longer ago than the retention period are purged
+    let pool = setup().await;
+    seed_test_data(&pool)
+        .await
+        .expect("Failed to seed test data");
+    trash_post(&pool, 1, 31).await;
+    trash_post(&pool, 2, 29).await;
+    let purged = resolvers::purge_expired_posts(&pool, TrashRetention { days: 30 })
+        .await
+        .unwrap();
+    assert_eq!(purged, 1);
+    assert_eq!(post_ids(&pool).await, vec![2, 3]);
+
+    let purged = resolvers::purge_expired_posts(&pool, TrashRetention { days: 7 })
+        .await
+        .unwrap();
+    assert_eq!(purged, 1);
+    assert_eq!(post_ids(&pool).await, vec![3]);
+}
@@ -16,7 +16,46 @@ mod test_utils {
     include!("../src/test_utils.rs");
 }
 
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use test_utils::{build_schema, seed_test_data, setup};
+use tracing::{Event, Metadata, span};
+
+// Counts the statements sqlx logs (target `sqlx::query`) while installed
+struct QueryCounter(Arc<AtomicUsize>);
+
+impl tracing::Subscriber for QueryCounter {
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        metadata.target() == "sqlx::query"
+    }
+    fn new_span(&self, _: &span::Attributes<'_>) -> span::Id {
+        span::Id::from_u64(1)
+    }
+    fn record(&self, _: &span::Id, _: &span::Record<'_>) {}
+    fn record_follows_from(&self, _: &span::Id, _: &span::Id) {}
+    fn event(&self, _: &Event<'_>) {
+        self.0.fetch_add(1, Ordering::SeqCst);
+    }
+    fn enter(&self, _: &span::Id) {}
+    fn exit(&self, _: &span::Id) {}
+}
+
+// Runs `query` and returns how many SQL statements it took
+async fn count_queries<Q, M>(
+    schema: &async_graphql::Schema<Q, M, async_graphql::EmptySubscription>,
+    query: &str,
+) -> usize
+where
+    Q: async_graphql::ObjectType + 'static,
+    M: async_graphql::ObjectType + 'static,
+{
+    let count = Arc::new(AtomicUsize::new(0));
+    // The test runtime is single-threaded, so loader tasks log on this thread too
+    let _guard = tracing::subscriber::set_default(QueryCounter(count.clone()));
+    let result = schema.execute(query).await;
+    assert!(result.errors.is_empty(), "{:?}", result.errors);
+    count.load(Ordering::SeqCst)
+}
 
 #[tokio::test]
 async fn test_user_posts_resolver() {
@@ -34,11 +73,13 @@ async fn test_user_posts_resolver() {
         query {
             users {
                 data {
-                    id
+                    databaseId
                     name
                     posts {
-                        id
-                        title
+                        data {
+                            databaseId
+                            title
+                        }
                     }
                 }
             }
@@ -53,9 +94,9 @@ async fn test_user_posts_resolver() {
     let users = data["users"]["data"].as_array().unwrap();
     let user1 = users
         .iter()
-        .find(|u| u["id"].as_i64().unwrap() == 1)
+        .find(|u| u["databaseId"].as_i64().unwrap() == 1)
         .unwrap();
-    let posts = user1["posts"].as_array().unwrap();
+    let posts = user1["posts"]["data"].as_array().unwrap();
     assert_eq!(posts.len(), 2);
 }
 
@@ -75,10 +116,10 @@ async fn test_post_user_resolver() {
         query {
             posts {
                 data {
-                    id
+                    databaseId
                     title
                     user {
-                        id
+                        databaseId
                         name
                         email
                     }
@@ -97,3 +138,332 @@ async fn test_post_user_resolver() {
     assert!(post1["user"].is_object());
     assert!(post1["user"]["name"].is_string());
 }
+
+#[tokio::test]
+async fn test_users_filtered_by_some_posts() {
+    // Test filtering users who have at least one matching post
+    let pool = setup().await;
+    seed_test_data(&pool)
+        .await
+        .expect("Failed to seed test data");
+    let schema = build_schema(
+        pool,
+        resolvers::Query::default(),
+        resolvers::Mutation::default(),
+    );
+    let query = r#"
+        query {
+            users(filters: { posts: { some: { title: { endsWith: "3" } } } }) {
+                data {
+                    databaseId
+                }
+                totalCount
+            }
+        }
+    "#;
+    let result = schema.execute(query).await;
+    assert!(result.errors.is_empty());
+    let data = result.data.into_json().unwrap();
+    let users = data["users"]["data"].as_array().unwrap();
+    assert_eq!(users.len(), 1);
+    assert_eq!(data["users"]["totalCount"].as_i64().unwrap(), 1);
+    assert_eq!(users[0]["databaseId"].as_i64().unwrap(), 2);
+}
+
+#[tokio::test]
+async fn test_users_filtered_by_no_posts() {
+    // Test filtering users without any posts using an empty `none` filter
+    let pool = setup().await;
+    seed_test_data(&pool)
+        .await
+        .expect("Failed to seed test data");
+    let schema = build_schema(
+        pool,
+        resolvers::Query::default(),
+        resolvers::Mutation::default(),
+    );
+    let query = r#"
+        query {
+            users(filters: { posts: { none: {} } }) {
+                data {
+                    databaseId
+                }
+                totalCount
+            }
+        }
+    "#;
+    let result = schema.execute(query).await;
+    assert!(result.errors.is_empty());
+    let data = result.data.into_json().unwrap();
+    let users = data["users"]["data"].as_array().unwrap();
+    assert_eq!(users.len(), 1);
+    assert_eq!(users[0]["databaseId"].as_i64().unwrap(), 3);
+}
+
+#[tokio::test]
+async fn test_users_filtered_by_every_post() {
+    // Test filtering users whose posts all match (users without posts included)
+    let pool = setup().await;
+    seed_test_data(&pool)
+        .await
+        .expect("Failed to seed test data");
+    let schema = build_schema(
+        pool,
+        resolvers::Query::default(),
+        resolvers::Mutation::default(),
+    );
+    let query = r#"
+        query {
+            users(filters: { posts: { every: { title: { endsWith: "1" } } } }) {
+                data {
+                    databaseId
+                }
+                totalCount
+            }
+        }
+    "#;
+    let result = schema.execute(query).await;
+    assert!(result.errors.is_empty());
+    let data = result.data.into_json().unwrap();
+    let users = data["users"]["data"].as_array().unwrap();
+    assert_eq!(users.len(), 1);
+    assert_eq!(users[0]["databaseId"].as_i64().unwrap(), 3);
+}
+
+#[tokio::test]
+async fn test_posts_filtered_by_author() {
+    // Test filtering posts by a condition on their author
+    let pool = setup().await;
+    seed_test_data(&pool)
+        .await
+        .expect("Failed to seed test data");
+    let schema = build_schema(
+        pool,
+        resolvers::Query::default(),
+        resolvers::Mutation::default(),
+    );
+    let query = r#"
+        query {
+            posts(filters: { user: { email: { equals: "test2@example.com" } } }) {
+                data {
+                    databaseId
+                    user {
+                        databaseId
+                    }
+                }
+                totalCount
+            }
+        }
+    "#;
+    let result = schema.execute(query).await;
+    assert!(result.errors.is_empty());
+    let data = result.data.into_json().unwrap();
+    let posts = data["posts"]["data"].as_array().unwrap();
+    assert_eq!(posts.len(), 1);
+    assert_eq!(data["posts"]["totalCount"].as_i64().unwrap(), 1);
+    assert_eq!(posts[0]["user"]["databaseId"].as_i64().unwrap(), 2);
+}
+
+#[tokio::test]
+async fn test_nested_relation_filters() {
+    // Test posts whose author has another post matching a title filter
+    let pool = setup().await;
+    seed_test_data(&pool)
+        .await
+        .expect("Failed to seed test data");
+    let schema = build_schema(
+        pool,
+        resolvers::Query::default(),
+        resolvers::Mutation::default(),
+    );
+    let query = r#"
+        query {
+            posts(filters: {
+                user: { posts: { some: { title: { equals: "Test Post 2" } } } }
+                id: { gt: 0 }
+            }) {
+                data {
+                    databaseId
+                }
+                totalCount
+            }
+        }
+    "#;
+    let result = schema.execute(query).await;
+    assert!(result.errors.is_empty());
+    let data = result.data.into_json().unwrap();
+    assert_eq!(data["posts"]["totalCount"].as_i64().unwrap(), 2);
+}
+
+#[tokio::test]
+async fn test_nested_page_query_count_is_constant() {
+    // Test User.posts and Post.user are batched, so more users don't mean more queries
+    let pool = setup().await;
+    seed_test_data(&pool)
+        .await
+        .expect("Failed to seed test data");
+    let schema = build_schema(
+        pool.clone(),
+        resolvers::Query::default(),
+        resolvers::Mutation::default(),
+    );
+    let query = r#"
+        query {
+            users(limit: 200) {
+                data {
+                    databaseId
+                    posts {
+                        data {
+                            databaseId
+                            user {
+                                databaseId
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    "#;
+
+    // Count, page, one batch of posts, one batch of authors
+    assert_eq!(count_queries(&schema, query).await, 4);
+
+    sqlx::query(
+        "WITH new_users AS (
+             INSERT INTO users (name) SELECT 'Bulk ' || n FROM generate_series(1, 30) n
+             RETURNING id
+         )
+         INSERT INTO posts (user_id, title) SELECT id, 'Bulk post' FROM new_users",
+    )
+    .execute(&pool)
+    .await
+    .expect("Failed to insert users");
+    assert_eq!(count_queries(&schema, query).await, 4);
+}
+
+#[tokio::test]
+async fn test_user_posts_page_filter_and_count() {
+    // Test User.posts applies filters, order and paging per user, with counts
+    let pool = setup().await;
+    seed_test_data(&pool)
+        .await
+        .expect("Failed to seed test data");
+    sqlx::query("INSERT INTO posts (user_id, title) VALUES (1, 'Extra'), (2, 'Extra')")
+        .execute(&pool)
+        .await
+        .expect("Failed to insert posts");
+    let schema = build_schema(
+        pool,
+        resolvers::Query::default(),
+        resolvers::Mutation::default(),
+    );
+    let query = r#"
+        query {
+            users(filters: { id: { lte: 3 } }) {
+                data {
+                    databaseId
+                    postsCount
+                    posts(
+                        filters: { title: { startsWith: "Test" } }
+                        orderBy: [{ id: { direction: DESC } }]
+                        limit: 1
+                        offset: 0
+                    ) {
+                        data {
+                            databaseId
+                        }
+                        totalCount
+                    }
+                }
+            }
+        }
+    "#;
+    let result = schema.execute(query).await;
+    assert!(result.errors.is_empty(), "{:?}", result.errors);
+    let data = result.data.into_json().unwrap();
+    let users = data["users"]["data"].as_array().unwrap();
+
+    // User 1 has posts 1, 2 and Extra; user 2 has post 3 and Extra; user 3 none
+    let summary: Vec<(i64, i64, i64, Vec<i64>)> = users
+        .iter()
+        .map(|u| {
+            let ids = u["posts"]["data"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|p| p["databaseId"].as_i64().unwrap())
+                .collect();
+            (
+                u["databaseId"].as_i64().unwrap(),
+                u["postsCount"].as_i64().unwrap(),
+                u["posts"]["totalCount"].as_i64().unwrap(),
+                ids,
+            )
+        })
+        .collect();
+    assert_eq!(
+        summary,
+        vec![(1, 3, 2, vec![2]), (2, 2, 1, vec![3]), (3, 0, 0, vec![]),]
+    );
+}
+
+#[tokio::test]
+async fn test_user_posts_with_counts_are_batched() {
+    // Test posts pages, their totals and postsCount add one query each, not one per user
+    let pool = setup().await;
+    seed_test_data(&pool)
+        .await
+        .expect("Failed to seed test data");
+    let schema = build_schema(
+        pool,
+        resolvers::Query::default(),
+        resolvers::Mutation::default(),
+    );
+    let query = r#"
+        query {
+            users {
+                data {
+                    postsCount
+                    posts(limit: 1) {
+                        data {
+                            databaseId
+                        }
+                        totalCount
+                    }
+                }
+            }
+        }
+    "#;
+
+    // Count, page, posts page, posts totals, posts counts
+    assert_eq!(count_queries(&schema, query).await, 5);
+}
+
+#[tokio::test]
+async fn test_nodes_are_batched_per_type() {
+    // Test nodes(ids:) takes one query per object type, not one per ID
+    let pool = setup().await;
+    seed_test_data(&pool)
+        .await
+        .expect("Failed to seed test data");
+    let schema = build_schema(
+        pool,
+        resolvers::Query::default(),
+        resolvers::Mutation::default(),
+    );
+    let ids = [
+        ("User", 1),
+        ("Post", 1),
+        ("User", 2),
+        ("Post", 2),
+        ("Post", 3),
+    ]
+    .iter()
+    .map(|(type_name, id)| format!("\"{}\"", utils::GlobalId::new(type_name, *id).encode()))
+    .collect::<Vec<_>>()
+    .join(", ");
+    let query = format!("query {{ nodes(ids: [{ids}]) {{ id }} }}");
+
+    // Users, posts
+    assert_eq!(count_queries(&schema, &query).await, 2);
+}
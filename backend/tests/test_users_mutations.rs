@@ -0,0 +1,365 @@
+// This file contains GraphQL integration tests for user mutations (create, update, delete).
+
+// Include utils module at crate root so resolvers can use crate::utils
+mod utils {
+    include!("../src/utils.rs");
+}
+
+// Include resolvers module directly at crate root
+// Now crate::utils in resolvers.rs will resolve to our utils module above
+mod resolvers {
+    include!("../src/resolvers.rs");
+}
+
+// Include test utilities
+mod test_utils {
+    include!("../src/test_utils.rs");
+}
+
+use sqlx::PgPool;
+use test_utils::{build_schema, seed_test_data, setup};
+
+// Post ids owned by `user_id`, in id order
+async fn post_ids_of(pool: &PgPool, user_id: i32) -> Vec<i32> {
+    sqlx::query_as::<_, (i32,)>("SELECT id FROM posts WHERE user_id = $1 ORDER BY id")
+        .bind(user_id)
+        .fetch_all(pool)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|(id,)| id)
+        .collect()
+}
+
+// ----- USER MUTATION TESTS -----
+
+#[tokio::test]
+async fn test_create_user_mutation() {
+    // Test creating a new user with valid data, trimming the inputs
+    let pool = setup().await;
+    seed_test_data(&pool)
+        .await
+        .expect("Failed to seed test data");
+    let schema = build_schema(
+        pool,
+        resolvers::Query::default(),
+        resolvers::Mutation::default(),
+    );
+    let mutation = r#"
+        mutation {
+            createUser(input: {
+                name: "  New User "
+                age: 41
+                email: " new.user@example.com "
+                phone: "+1 (555) 010-9999"
+            }) {
+                databaseId
+                name
+                age
+                email
+                phone
+                postsCount
+            }
+        }
+    "#;
+    let result = schema.execute(mutation).await;
+    assert!(result.errors.is_empty(), "{:?}", result.errors);
+    let data = result.data.into_json().unwrap();
+    let user = &data["createUser"];
+    assert_eq!(user["databaseId"], 4);
+    assert_eq!(user["name"], "New User");
+    assert_eq!(user["age"], 41);
+    assert_eq!(user["email"], "new.user@example.com");
+    assert_eq!(user["phone"], "+1 (555) 010-9999");
+    assert_eq!(user["postsCount"], 0);
+}
+
+#[tokio::test]
+async fn test_create_user_mutation_rejects_invalid_input() {
+    // Test each field is validated before anything is written
+    let pool = setup().await;
+    seed_test_data(&pool)
+        .await
+        .expect("Failed to seed test data");
+    let schema = build_schema(
+        pool.clone(),
+        resolvers::Query::default(),
+        resolvers::Mutation::default(),
+    );
+    let cases = [
+        (
+            r#"name: " ", age: 30, email: "a@example.com""#,
+            "Name cannot be empty",
+        ),
+        (
+            r#"name: "A", age: 151, email: "a@example.com""#,
+            "Age must be between 0 and 150",
+        ),
+        (
+            r#"name: "A", age: -1, email: "a@example.com""#,
+            "Age must be between 0 and 150",
+        ),
+        (
+            r#"name: "A", age: 30, email: "not-an-email""#,
+            "Invalid email address",
+        ),
+        (
+            r#"name: "A", age: 30, email: "a@example.com", phone: "call me""#,
+            "Invalid phone number",
+        ),
+    ];
+    for (input, message) in cases {
+        let mutation = format!("mutation {{ createUser(input: {{ {input} }}) {{ databaseId }} }}");
+        let result = schema.execute(mutation.as_str()).await;
+        assert_eq!(result.errors.len(), 1, "{input}");
+        assert!(result.errors[0].message.contains(message), "{input}");
+    }
+
+    let (count,) = sqlx::query_as::<_, (i64,)>("SELECT COUNT(*) FROM users")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(count, 3);
+}
+
+#[tokio::test]
+async fn test_create_user_mutation_duplicate_email() {
+    // Test the email UNIQUE constraint surfaces as a readable error
+    let pool = setup().await;
+    seed_test_data(&pool)
+        .await
+        .expect("Failed to seed test data");
+    let schema = build_schema(
+        pool,
+        resolvers::Query::default(),
+        resolvers::Mutation::default(),
+    );
+    let mutation = r#"
+        mutation {
+            createUser(input: { name: "Copy", age: 20, email: "test1@example.com" }) {
+                databaseId
+            }
+        }
+    "#;
+    let result = schema.execute(mutation).await;
+    assert_eq!(result.errors.len(), 1);
+    assert_eq!(
+        result.errors[0].message,
+        "Email is already in use: test1@example.com"
+    );
+    let extensions = result.errors[0].extensions.as_ref().unwrap();
+    assert_eq!(extensions.get("code"), Some(&"CONFLICT".into()));
+    assert_eq!(extensions.get("field"), Some(&"email".into()));
+}
+
+#[tokio::test]
+async fn test_email_uniqueness_ignores_case() {
+    // Test an email differing from a taken one only in case is rejected,
+    // while a user can still change the case of their own
+    let pool = setup().await;
+    seed_test_data(&pool)
+        .await
+        .expect("Failed to seed test data");
+    let schema = build_schema(
+        pool,
+        resolvers::Query::default(),
+        resolvers::Mutation::default(),
+    );
+    let cases = [
+        (
+            r#"createUser(input: { name: "Copy", age: 20, email: "TEST1@example.com" }) { databaseId }"#,
+            "Email is already in use: TEST1@example.com",
+        ),
+        (
+            r#"updateUser(input: { id: 1, email: "Test2@Example.com" }) { databaseId }"#,
+            "Email is already in use: Test2@Example.com",
+        ),
+    ];
+    for (mutation, message) in cases {
+        let result = schema
+            .execute(format!("mutation {{ {mutation} }}").as_str())
+            .await;
+        assert_eq!(result.errors.len(), 1, "{mutation}");
+        assert_eq!(result.errors[0].message, message);
+        let extensions = result.errors[0].extensions.as_ref().unwrap();
+        assert_eq!(extensions.get("code"), Some(&"CONFLICT".into()));
+        assert_eq!(extensions.get("field"), Some(&"email".into()));
+    }
+
+    let result = schema
+        .execute(
+            r#"mutation { updateUser(input: { id: 2, email: "TEST2@example.com" }) { email } }"#,
+        )
+        .await;
+    assert!(result.errors.is_empty(), "{:?}", result.errors);
+    assert_eq!(
+        result.data.into_json().unwrap()["updateUser"]["email"],
+        "TEST2@example.com"
+    );
+}
+
+#[tokio::test]
+async fn test_update_user_mutation() {
+    // Test updating some fields leaves the others alone and empty phone clears it
+    let pool = setup().await;
+    seed_test_data(&pool)
+        .await
+        .expect("Failed to seed test data");
+    let schema = build_schema(
+        pool,
+        resolvers::Query::default(),
+        resolvers::Mutation::default(),
+    );
+    let mutation = r#"
+        mutation {
+            updateUser(input: { id: 2, age: 31, phone: "" }) {
+                databaseId
+                name
+                age
+                email
+                phone
+            }
+        }
+    "#;
+    let result = schema.execute(mutation).await;
+    assert!(result.errors.is_empty(), "{:?}", result.errors);
+    let data = result.data.into_json().unwrap();
+    let user = &data["updateUser"];
+    assert_eq!(user["name"], "Test User 2");
+    assert_eq!(user["age"], 31);
+    assert_eq!(user["email"], "test2@example.com");
+    assert!(user["phone"].is_null());
+}
+
+#[tokio::test]
+async fn test_update_user_mutation_errors() {
+    // Test empty updates, unknown users, invalid values and taken emails are rejected
+    let pool = setup().await;
+    seed_test_data(&pool)
+        .await
+        .expect("Failed to seed test data");
+    let schema = build_schema(
+        pool,
+        resolvers::Query::default(),
+        resolvers::Mutation::default(),
+    );
+    let cases = [
+        ("id: 1", "Nothing to update"),
+        ("id: 999, age: 20", "User 999 not found"),
+        ("id: 1, age: 200", "Age must be between 0 and 150"),
+        (
+            r#"id: 1, email: "test2@example.com""#,
+            "Email is already in use: test2@example.com",
+        ),
+    ];
+    for (input, message) in cases {
+        let mutation = format!("mutation {{ updateUser(input: {{ {input} }}) {{ databaseId }} }}");
+        let result = schema.execute(mutation.as_str()).await;
+        assert_eq!(result.errors.len(), 1, "{input}");
+        assert!(result.errors[0].message.contains(message), "{input}");
+    }
+}
+
+#[tokio::test]
+async fn test_delete_user_refuses_when_posts_exist() {
+    // Test the default delete keeps a user who still has posts
+    let pool = setup().await;
+    seed_test_data(&pool)
+        .await
+        .expect("Failed to seed test data");
+    let schema = build_schema(
+        pool.clone(),
+        resolvers::Query::default(),
+        resolvers::Mutation::default(),
+    );
+    let result = schema.execute("mutation { deleteUser(id: 1) }").await;
+    assert_eq!(result.errors.len(), 1);
+    assert!(result.errors[0].message.contains("User 1 has 2 posts"));
+    assert_eq!(post_ids_of(&pool, 1).await, vec![1, 2]);
+
+    // A user without posts is deleted; an unknown one reports false
+    let result = schema
+        .execute("mutation { a: deleteUser(id: 3) b: deleteUser(id: 999) }")
+        .await;
+    assert!(result.errors.is_empty(), "{:?}", result.errors);
+    let data = result.data.into_json().unwrap();
+    assert_eq!(data["a"], true);
+    assert_eq!(data["b"], false);
+}
+
+#[tokio::test]
+async fn test_delete_user_cascade() {
+    // Test CASCADE deletes the user's posts with them
+    let pool = setup().await;
+    seed_test_data(&pool)
+        .await
+        .expect("Failed to seed test data");
+    let schema = build_schema(
+        pool.clone(),
+        resolvers::Query::default(),
+        resolvers::Mutation::default(),
+    );
+    let result = schema
+        .execute("mutation { deleteUser(id: 1, posts: CASCADE) }")
+        .await;
+    assert!(result.errors.is_empty(), "{:?}", result.errors);
+    assert_eq!(result.data.into_json().unwrap()["deleteUser"], true);
+    assert!(post_ids_of(&pool, 1).await.is_empty());
+    assert_eq!(post_ids_of(&pool, 2).await, vec![3]);
+}
+
+#[tokio::test]
+async fn test_delete_user_reassign() {
+    // Test REASSIGN moves the posts to another user before deleting
+    let pool = setup().await;
+    seed_test_data(&pool)
+        .await
+        .expect("Failed to seed test data");
+    let schema = build_schema(
+        pool.clone(),
+        resolvers::Query::default(),
+        resolvers::Mutation::default(),
+    );
+    let result = schema
+        .execute("mutation { deleteUser(id: 1, posts: REASSIGN, reassignTo: 2) }")
+        .await;
+    assert!(result.errors.is_empty(), "{:?}", result.errors);
+    assert_eq!(result.data.into_json().unwrap()["deleteUser"], true);
+    assert_eq!(post_ids_of(&pool, 2).await, vec![1, 2, 3]);
+}
+
+#[tokio::test]
+async fn test_delete_user_reassign_errors() {
+    // Test bad reassign arguments are rejected and nothing is deleted
+    let pool = setup().await;
+    seed_test_data(&pool)
+        .await
+        .expect("Failed to seed test data");
+    let schema = build_schema(
+        pool.clone(),
+        resolvers::Query::default(),
+        resolvers::Mutation::default(),
+    );
+    let cases = [
+        ("posts: REASSIGN", "reassignTo is required"),
+        (
+            "posts: REASSIGN, reassignTo: 1",
+            "Cannot reassign posts to the user being deleted",
+        ),
+        (
+            "posts: REASSIGN, reassignTo: 999",
+            "User 999 to reassign posts to not found",
+        ),
+        (
+            "posts: CASCADE, reassignTo: 2",
+            "reassignTo is only allowed",
+        ),
+    ];
+    for (args, message) in cases {
+        let mutation = format!("mutation {{ deleteUser(id: 1, {args}) }}");
+        let result = schema.execute(mutation.as_str()).await;
+        assert_eq!(result.errors.len(), 1, "{args}");
+        assert!(result.errors[0].message.contains(message), "{args}");
+    }
+    assert_eq!(post_ids_of(&pool, 1).await, vec![1, 2]);
+}
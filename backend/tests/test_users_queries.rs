@@ -36,7 +36,7 @@ async fn test_users_query_with_id_filter() {
         query {
             users(filters: { id: { equals: 2 } }) {
                 data {
-                    id
+                    databaseId
                     name
                 }
                 totalCount
@@ -49,7 +49,7 @@ async fn test_users_query_with_id_filter() {
     let users = data["users"]["data"].as_array().unwrap();
     assert_eq!(users.len(), 1);
     assert_eq!(data["users"]["totalCount"].as_i64().unwrap(), 1);
-    assert_eq!(users[0]["id"].as_i64().unwrap(), 2);
+    assert_eq!(users[0]["databaseId"].as_i64().unwrap(), 2);
 }
 
 #[tokio::test]
@@ -68,7 +68,7 @@ async fn test_users_query_with_age_equals() {
         query {
             users(filters: { age: { equals: 25 } }) {
                 data {
-                    id
+                    databaseId
                     name
                     age
                 }
@@ -100,7 +100,7 @@ async fn test_users_query_with_email_filter() {
         query {
             users(filters: { email: { equals: "test1@example.com" } }) {
                 data {
-                    id
+                    databaseId
                     name
                     email
                 }
@@ -132,7 +132,7 @@ async fn test_users() {
         query {
             users {
                 data {
-                    id
+                    databaseId
                     name
                     email
                 }
@@ -167,7 +167,7 @@ async fn test_users_query_with_pagination() {
         query {
             users(limit: 2, offset: 0) {
                 data {
-                    id
+                    databaseId
                 }
                 totalCount
             }
@@ -199,7 +199,7 @@ async fn test_users_query_with_age_gte() {
         query {
             users(filters: { age: { gte: 30 } }) {
                 data {
-                    id
+                    databaseId
                     age
                 }
                 totalCount
@@ -233,7 +233,7 @@ async fn test_users_query_with_age_lte() {
         query {
             users(filters: { age: { lte: 30 } }) {
                 data {
-                    id
+                    databaseId
                     age
                 }
                 totalCount
@@ -267,7 +267,7 @@ async fn test_users_query_with_age_gt() {
         query {
             users(filters: { age: { gt: 25 } }) {
                 data {
-                    id
+                    databaseId
                     age
                 }
                 totalCount
@@ -301,7 +301,7 @@ async fn test_users_query_with_age_lt() {
         query {
             users(filters: { age: { lt: 35 } }) {
                 data {
-                    id
+                    databaseId
                     age
                 }
                 totalCount
@@ -335,7 +335,7 @@ async fn test_users_query_with_age_range() {
         query {
             users(filters: { age: { gte: 25, lte: 35 } }) {
                 data {
-                    id
+                    databaseId
                     age
                 }
                 totalCount
@@ -368,7 +368,7 @@ async fn test_users_query_with_name_equals() {
         query {
             users(filters: { name: { equals: "Test User 1" } }) {
                 data {
-                    id
+                    databaseId
                     name
                 }
                 totalCount
@@ -400,7 +400,7 @@ async fn test_users_query_with_name_contains() {
         query {
             users(filters: { name: { contains: "User 1" } }) {
                 data {
-                    id
+                    databaseId
                     name
                 }
                 totalCount
@@ -432,7 +432,7 @@ async fn test_users_query_with_name_starts_with() {
         query {
             users(filters: { name: { startsWith: "Test" } }) {
                 data {
-                    id
+                    databaseId
                     name
                 }
                 totalCount
@@ -466,7 +466,7 @@ async fn test_users_query_with_name_ends_with() {
         query {
             users(filters: { name: { endsWith: "User 2" } }) {
                 data {
-                    id
+                    databaseId
                     name
                 }
                 totalCount
@@ -498,7 +498,7 @@ async fn test_users_query_with_name_contains_insensitive() {
         query {
             users(filters: { name: { containsInsensitive: "USER 3" } }) {
                 data {
-                    id
+                    databaseId
                     name
                 }
                 totalCount
@@ -530,7 +530,7 @@ async fn test_users_query_with_email_equals() {
         query {
             users(filters: { email: { equals: "test1@example.com" } }) {
                 data {
-                    id
+                    databaseId
                     email
                 }
                 totalCount
@@ -562,7 +562,7 @@ async fn test_users_query_with_email_contains() {
         query {
             users(filters: { email: { contains: "test" } }) {
                 data {
-                    id
+                    databaseId
                     email
                 }
                 totalCount
@@ -596,7 +596,7 @@ async fn test_users_query_with_email_starts_with() {
         query {
             users(filters: { email: { startsWith: "test1" } }) {
                 data {
-                    id
+                    databaseId
                     email
                 }
                 totalCount
@@ -628,7 +628,7 @@ async fn test_users_query_with_email_ends_with() {
         query {
             users(filters: { email: { endsWith: "@example.com" } }) {
                 data {
-                    id
+                    databaseId
                     email
                 }
                 totalCount
@@ -662,7 +662,7 @@ async fn test_users_query_with_email_contains_insensitive() {
         query {
             users(filters: { email: { containsInsensitive: "TEST1" } }) {
                 data {
-                    id
+                    databaseId
                     email
                 }
                 totalCount
@@ -694,7 +694,7 @@ async fn test_users_query_with_phone_filter() {
         query {
             users(filters: { phone: { equals: "111-111-1111" } }) {
                 data {
-                    id
+                    databaseId
                     phone
                 }
                 totalCount
@@ -726,7 +726,7 @@ async fn test_users_query_with_phone_equals() {
         query {
             users(filters: { phone: { equals: "111-111-1111" } }) {
                 data {
-                    id
+                    databaseId
                     phone
                 }
                 totalCount
@@ -758,7 +758,7 @@ async fn test_users_query_with_phone_contains() {
         query {
             users(filters: { phone: { contains: "111" } }) {
                 data {
-                    id
+                    databaseId
                     phone
                 }
                 totalCount
@@ -790,7 +790,7 @@ async fn test_users_query_with_phone_starts_with() {
         query {
             users(filters: { phone: { startsWith: "222" } }) {
                 data {
-                    id
+                    databaseId
                     phone
                 }
                 totalCount
@@ -822,7 +822,7 @@ async fn test_users_query_with_phone_ends_with() {
         query {
             users(filters: { phone: { endsWith: "1111" } }) {
                 data {
-                    id
+                    databaseId
                     phone
                 }
                 totalCount
@@ -854,7 +854,7 @@ async fn test_users_query_with_phone_contains_insensitive() {
         query {
             users(filters: { phone: { containsInsensitive: "222-222" } }) {
                 data {
-                    id
+                    databaseId
                     phone
                 }
                 totalCount
@@ -886,7 +886,7 @@ async fn test_users_query_with_name_equals_exact() {
         query {
             users(filters: { name: { equals: "Test User 2" } }) {
                 data {
-                    id
+                    databaseId
                     name
                 }
                 totalCount
@@ -918,7 +918,7 @@ async fn test_users_query_with_phone_equals_exact() {
         query {
             users(filters: { phone: { equals: "222-222-2222" } }) {
                 data {
-                    id
+                    databaseId
                     phone
                 }
                 totalCount
@@ -950,7 +950,7 @@ async fn test_users_query_with_email_equals_exact() {
         query {
             users(filters: { email: { equals: "test2@example.com" } }) {
                 data {
-                    id
+                    databaseId
                     email
                 }
                 totalCount
@@ -987,7 +987,7 @@ async fn test_users_query_with_or_filter() {
                 { age: { equals: 35 } }
             ] }) {
                 data {
-                    id
+                    databaseId
                     age
                 }
                 totalCount
@@ -1024,7 +1024,7 @@ async fn test_users_query_with_and_filter() {
                 { age: { lte: 30 } }
             ] }) {
                 data {
-                    id
+                    databaseId
                     age
                 }
                 totalCount
@@ -1039,7 +1039,7 @@ async fn test_users_query_with_and_filter() {
     assert_eq!(data["users"]["totalCount"].as_i64().unwrap(), 2);
     for user in users {
         let age = user["age"].as_i64().unwrap();
-        assert!(age >= 25 && age <= 30);
+        assert!((25..=30).contains(&age));
     }
 }
 
@@ -1062,7 +1062,7 @@ async fn test_users_query_with_multiple_filters() {
                 email: { contains: "test1" }
             }) {
                 data {
-                    id
+                    databaseId
                     age
                     email
                 }
@@ -1096,7 +1096,7 @@ async fn test_users_query_with_filters() {
         query {
             users(filters: { age: { equals: 25 } }) {
                 data {
-                    id
+                    databaseId
                     name
                     age
                 }
@@ -1128,7 +1128,7 @@ async fn test_users_query_with_empty_result() {
         query {
             users(filters: { age: { equals: 99 } }) {
                 data {
-                    id
+                    databaseId
                 }
                 totalCount
             }
@@ -1141,3 +1141,604 @@ async fn test_users_query_with_empty_result() {
     assert_eq!(users.len(), 0);
     assert_eq!(data["users"]["totalCount"].as_i64().unwrap(), 0);
 }
+
+#[tokio::test]
+async fn test_users_query_with_updated_at_on_or_after() {
+    // Test filtering users updated on or after a given instant
+    let pool = setup().await;
+    seed_test_data(&pool)
+        .await
+        .expect("Failed to seed test data");
+    // Bypass the updated_at trigger so explicit timestamps stick
+    sqlx::query("ALTER TABLE users DISABLE TRIGGER update_users_updated_at")
+        .execute(&pool)
+        .await
+        .expect("Failed to disable trigger");
+    sqlx::query(
+        "UPDATE users SET updated_at = CASE WHEN id = 3 \
+         THEN '2025-02-01T00:00:00Z'::timestamptz ELSE '2024-01-01T00:00:00Z'::timestamptz END",
+    )
+    .execute(&pool)
+    .await
+    .expect("Failed to update users");
+    sqlx::query("ALTER TABLE users ENABLE TRIGGER update_users_updated_at")
+        .execute(&pool)
+        .await
+        .expect("Failed to enable trigger");
+    let schema = build_schema(
+        pool,
+        resolvers::Query::default(),
+        resolvers::Mutation::default(),
+    );
+    let query = r#"
+        query {
+            users(filters: { updatedAt: { onOrAfter: "2025-01-01T00:00:00Z" } }) {
+                data {
+                    databaseId
+                    name
+                }
+                totalCount
+            }
+        }
+    "#;
+    let result = schema.execute(query).await;
+    assert!(result.errors.is_empty());
+    let data = result.data.into_json().unwrap();
+    let users = data["users"]["data"].as_array().unwrap();
+    assert_eq!(users.len(), 1);
+    assert_eq!(data["users"]["totalCount"].as_i64().unwrap(), 1);
+    assert_eq!(users[0]["databaseId"].as_i64().unwrap(), 3);
+}
+
+#[tokio::test]
+async fn test_users_query_with_email_in() {
+    // Test filtering users by a list of exact emails
+    let pool = setup().await;
+    seed_test_data(&pool)
+        .await
+        .expect("Failed to seed test data");
+    let schema = build_schema(
+        pool,
+        resolvers::Query::default(),
+        resolvers::Mutation::default(),
+    );
+    let query = r#"
+        query {
+            users(filters: { email: { in: ["test1@example.com", "test3@example.com"] } }) {
+                data {
+                    databaseId
+                }
+                totalCount
+            }
+        }
+    "#;
+    let result = schema.execute(query).await;
+    assert!(result.errors.is_empty());
+    let data = result.data.into_json().unwrap();
+    let users = data["users"]["data"].as_array().unwrap();
+    assert_eq!(users.len(), 2);
+    assert_eq!(data["users"]["totalCount"].as_i64().unwrap(), 2);
+    assert_eq!(users[0]["databaseId"].as_i64().unwrap(), 1);
+    assert_eq!(users[1]["databaseId"].as_i64().unwrap(), 3);
+}
+
+#[tokio::test]
+async fn test_users_query_with_phone_is_null() {
+    // Test filtering users without a phone number
+    let pool = setup().await;
+    seed_test_data(&pool)
+        .await
+        .expect("Failed to seed test data");
+    sqlx::query("UPDATE users SET phone = NULL WHERE id = 2")
+        .execute(&pool)
+        .await
+        .expect("Failed to update user");
+    let schema = build_schema(
+        pool,
+        resolvers::Query::default(),
+        resolvers::Mutation::default(),
+    );
+    let query = r#"
+        query {
+            users(filters: { phone: { isNull: true } }) {
+                data {
+                    databaseId
+                    phone
+                }
+                totalCount
+            }
+        }
+    "#;
+    let result = schema.execute(query).await;
+    assert!(result.errors.is_empty());
+    let data = result.data.into_json().unwrap();
+    let users = data["users"]["data"].as_array().unwrap();
+    assert_eq!(users.len(), 1);
+    assert_eq!(data["users"]["totalCount"].as_i64().unwrap(), 1);
+    assert_eq!(users[0]["databaseId"].as_i64().unwrap(), 2);
+}
+
+#[tokio::test]
+async fn test_users_query_with_order_by() {
+    // Test sorting users by age descending
+    let pool = setup().await;
+    seed_test_data(&pool)
+        .await
+        .expect("Failed to seed test data");
+    let schema = build_schema(
+        pool,
+        resolvers::Query::default(),
+        resolvers::Mutation::default(),
+    );
+    let query = r#"
+        query {
+            users(orderBy: [{ age: { direction: DESC } }]) {
+                data {
+                    databaseId
+                    age
+                }
+                totalCount
+            }
+        }
+    "#;
+    let result = schema.execute(query).await;
+    assert!(result.errors.is_empty());
+    let data = result.data.into_json().unwrap();
+    let users = data["users"]["data"].as_array().unwrap();
+    let ids: Vec<i64> = users
+        .iter()
+        .map(|u| u["databaseId"].as_i64().unwrap())
+        .collect();
+    assert_eq!(ids, vec![3, 2, 1]);
+}
+
+#[tokio::test]
+async fn test_users_query_with_order_by_nulls_and_tie_breaker() {
+    // Test nulls placement and the id tie-breaker on equal sort keys
+    let pool = setup().await;
+    seed_test_data(&pool)
+        .await
+        .expect("Failed to seed test data");
+    sqlx::query("UPDATE users SET age = NULL WHERE id = 2")
+        .execute(&pool)
+        .await
+        .expect("Failed to update user");
+    sqlx::query("UPDATE users SET age = 35 WHERE id = 1")
+        .execute(&pool)
+        .await
+        .expect("Failed to update user");
+    let schema = build_schema(
+        pool,
+        resolvers::Query::default(),
+        resolvers::Mutation::default(),
+    );
+    let query = r#"
+        query {
+            users(orderBy: [{ age: { direction: ASC, nulls: FIRST } }]) {
+                data {
+                    databaseId
+                }
+            }
+        }
+    "#;
+    let result = schema.execute(query).await;
+    assert!(result.errors.is_empty());
+    let data = result.data.into_json().unwrap();
+    let users = data["users"]["data"].as_array().unwrap();
+    let ids: Vec<i64> = users
+        .iter()
+        .map(|u| u["databaseId"].as_i64().unwrap())
+        .collect();
+    assert_eq!(ids, vec![2, 1, 3]);
+}
+
+#[tokio::test]
+async fn test_users_query_with_email_contains_underscore() {
+    // Test an underscore in a contains search matches only a literal underscore
+    let pool = setup().await;
+    seed_test_data(&pool)
+        .await
+        .expect("Failed to seed test data");
+    sqlx::query(
+        "UPDATE users SET email = CASE id \
+            WHEN 1 THEN 'first_last@example.com' \
+            WHEN 2 THEN 'firstXlast@example.com' \
+            ELSE email END",
+    )
+    .execute(&pool)
+    .await
+    .expect("Failed to update users");
+    let schema = build_schema(
+        pool,
+        resolvers::Query::default(),
+        resolvers::Mutation::default(),
+    );
+    let query = r#"
+        query {
+            users(filters: { email: { contains: "first_last" } }) {
+                data {
+                    databaseId
+                }
+                totalCount
+            }
+        }
+    "#;
+    let result = schema.execute(query).await;
+    assert!(result.errors.is_empty());
+    let data = result.data.into_json().unwrap();
+    let users = data["users"]["data"].as_array().unwrap();
+    assert_eq!(data["users"]["totalCount"].as_i64().unwrap(), 1);
+    assert_eq!(users[0]["databaseId"].as_i64().unwrap(), 1);
+}
+
+#[tokio::test]
+async fn test_users_query_with_email_matches_insensitive() {
+    // Test regex matching on email, case-insensitively
+    let pool = setup().await;
+    seed_test_data(&pool)
+        .await
+        .expect("Failed to seed test data");
+    let schema = build_schema(
+        pool,
+        resolvers::Query::default(),
+        resolvers::Mutation::default(),
+    );
+    let query = r#"
+        query {
+            users(filters: { email: { matchesInsensitive: "^TEST[13]@" } }) {
+                data {
+                    databaseId
+                }
+                totalCount
+            }
+        }
+    "#;
+    let result = schema.execute(query).await;
+    assert!(result.errors.is_empty());
+    let data = result.data.into_json().unwrap();
+    let users = data["users"]["data"].as_array().unwrap();
+    let ids: Vec<i64> = users
+        .iter()
+        .map(|u| u["databaseId"].as_i64().unwrap())
+        .collect();
+    assert_eq!(ids, vec![1, 3]);
+}
+
+#[tokio::test]
+async fn test_users_query_rejects_invalid_regex() {
+    // Test a malformed pattern is a validation error on filters, not an internal one
+    let pool = setup().await;
+    let schema = build_schema(
+        pool,
+        resolvers::Query::default(),
+        resolvers::Mutation::default(),
+    );
+    for operator in ["matches", "matchesInsensitive"] {
+        let query = format!(
+            r#"query {{ users(filters: {{ name: {{ {}: "(" }} }}) {{ totalCount }} }}"#,
+            operator
+        );
+        let result = schema.execute(query.as_str()).await;
+        assert_eq!(result.errors.len(), 1);
+        let extensions = result.errors[0].extensions.as_ref().unwrap();
+        assert_eq!(
+            extensions.get("code"),
+            Some(&async_graphql::Value::from("VALIDATION_FAILED"))
+        );
+        assert_eq!(
+            extensions.get("fields").unwrap(),
+            &async_graphql::value!([{
+                "field": "filters",
+                "message": "Invalid regular expression: parentheses () not balanced"
+            }])
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_users_query_with_email_equals_insensitive() {
+    // Test exact email lookup ignoring case
+    let pool = setup().await;
+    seed_test_data(&pool)
+        .await
+        .expect("Failed to seed test data");
+    let schema = build_schema(
+        pool,
+        resolvers::Query::default(),
+        resolvers::Mutation::default(),
+    );
+    let query = r#"
+        query {
+            users(filters: { email: { equals: "TEST2@Example.COM", mode: INSENSITIVE } }) {
+                data {
+                    databaseId
+                    email
+                }
+                totalCount
+            }
+        }
+    "#;
+    let result = schema.execute(query).await;
+    assert!(result.errors.is_empty());
+    let data = result.data.into_json().unwrap();
+    let users = data["users"]["data"].as_array().unwrap();
+    assert_eq!(data["users"]["totalCount"].as_i64().unwrap(), 1);
+    assert_eq!(users[0]["email"].as_str().unwrap(), "test2@example.com");
+}
+
+#[tokio::test]
+async fn test_users_query_with_name_in_insensitive() {
+    // Test list membership ignoring case
+    let pool = setup().await;
+    seed_test_data(&pool)
+        .await
+        .expect("Failed to seed test data");
+    let schema = build_schema(
+        pool,
+        resolvers::Query::default(),
+        resolvers::Mutation::default(),
+    );
+    let query = r#"
+        query {
+            users(filters: { name: { in: ["test user 1", "TEST USER 3"], mode: INSENSITIVE } }) {
+                data {
+                    databaseId
+                }
+            }
+        }
+    "#;
+    let result = schema.execute(query).await;
+    assert!(result.errors.is_empty());
+    let data = result.data.into_json().unwrap();
+    let users = data["users"]["data"].as_array().unwrap();
+    let ids: Vec<i64> = users
+        .iter()
+        .map(|u| u["databaseId"].as_i64().unwrap())
+        .collect();
+    assert_eq!(ids, vec![1, 3]);
+}
+
+#[tokio::test]
+async fn test_users_query_with_name_similar_to() {
+    // Test typo-tolerant name search returns the closest match first
+    let pool = setup().await;
+    seed_test_data(&pool)
+        .await
+        .expect("Failed to seed test data");
+    sqlx::query(
+        "UPDATE users SET name = CASE id \
+            WHEN 1 THEN 'John Doe' \
+            WHEN 2 THEN 'Jane Smith' \
+            ELSE 'Johnny Doe' END",
+    )
+    .execute(&pool)
+    .await
+    .expect("Failed to update users");
+    let schema = build_schema(
+        pool,
+        resolvers::Query::default(),
+        resolvers::Mutation::default(),
+    );
+    let query = r#"
+        query {
+            users(filters: { name: { similarTo: { value: "Jon Doe" } } }) {
+                data {
+                    databaseId
+                    similarity
+                }
+                totalCount
+            }
+        }
+    "#;
+    let result = schema.execute(query).await;
+    assert!(result.errors.is_empty());
+    let data = result.data.into_json().unwrap();
+    let users = data["users"]["data"].as_array().unwrap();
+    let ids: Vec<i64> = users
+        .iter()
+        .map(|u| u["databaseId"].as_i64().unwrap())
+        .collect();
+    assert_eq!(ids, vec![1, 3]);
+    assert!(users[0]["similarity"].as_f64().unwrap() > users[1]["similarity"].as_f64().unwrap());
+}
+
+#[tokio::test]
+async fn test_users_query_with_name_similar_to_threshold() {
+    // Test a lower similarity threshold finds misspelled surnames
+    let pool = setup().await;
+    seed_test_data(&pool)
+        .await
+        .expect("Failed to seed test data");
+    sqlx::query("UPDATE users SET name = 'Jane Smith' WHERE id = 2")
+        .execute(&pool)
+        .await
+        .expect("Failed to update user");
+    let schema = build_schema(
+        pool,
+        resolvers::Query::default(),
+        resolvers::Mutation::default(),
+    );
+    let query = r#"
+        query {
+            users(filters: { name: { similarTo: { value: "Smyth", threshold: 0.2 } } }) {
+                data {
+                    databaseId
+                }
+                totalCount
+            }
+        }
+    "#;
+    let result = schema.execute(query).await;
+    assert!(result.errors.is_empty());
+    let data = result.data.into_json().unwrap();
+    assert_eq!(data["users"]["totalCount"].as_i64().unwrap(), 1);
+    assert_eq!(data["users"]["data"][0]["databaseId"].as_i64().unwrap(), 2);
+}
+
+#[tokio::test]
+async fn test_users_query_rejects_similarity_threshold_out_of_range() {
+    // Test thresholds outside 0..=1 are rejected instead of matching everything or nothing
+    let pool = setup().await;
+    let schema = build_schema(
+        pool,
+        resolvers::Query::default(),
+        resolvers::Mutation::default(),
+    );
+    for (threshold, message) in [
+        ("-0.5", "must be greater than or equal to 0"),
+        ("1.5", "must be less than or equal to 1"),
+    ] {
+        let query = format!(
+            r#"query {{ users(filters: {{ name: {{ similarTo: {{ value: "Jon", threshold: {} }} }} }}) {{ totalCount }} }}"#,
+            threshold
+        );
+        let result = schema.execute(query.as_str()).await;
+        assert_eq!(result.errors.len(), 1, "{threshold}");
+        assert!(
+            result.errors[0].message.contains(message),
+            "{}",
+            result.errors[0].message
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_users_query_with_nested_name_similar_to_ranks_results() {
+    // Test the frontend's age + search filter, with similarTo nested in an or
+    // inside an and, still orders users by similarity
+    let pool = setup().await;
+    seed_test_data(&pool)
+        .await
+        .expect("Failed to seed test data");
+    sqlx::query(
+        "UPDATE users SET name = CASE id \
+            WHEN 1 THEN 'Johnny Doe' \
+            WHEN 2 THEN 'Jane Smith' \
+            ELSE 'John Doe' END",
+    )
+    .execute(&pool)
+    .await
+    .expect("Failed to update users");
+    let schema = build_schema(
+        pool,
+        resolvers::Query::default(),
+        resolvers::Mutation::default(),
+    );
+    let query = r#"
+        query {
+            users(filters: {
+                and: [
+                    { age: { gte: 20 } },
+                    {
+                        or: [
+                            { name: { containsInsensitive: "Jon Doe" } },
+                            { email: { containsInsensitive: "Jon Doe" } },
+                            { phone: { containsInsensitive: "Jon Doe" } },
+                            { name: { similarTo: { value: "Jon Doe", threshold: 0.2 } } }
+                        ]
+                    }
+                ]
+            }) {
+                data {
+                    databaseId
+                    similarity
+                }
+                totalCount
+            }
+        }
+    "#;
+    let result = schema.execute(query).await;
+    assert!(result.errors.is_empty(), "{:?}", result.errors);
+    let data = result.data.into_json().unwrap();
+    let users = data["users"]["data"].as_array().unwrap();
+    let ids: Vec<i64> = users
+        .iter()
+        .map(|u| u["databaseId"].as_i64().unwrap())
+        .collect();
+    assert_eq!(ids, vec![3, 1]);
+    assert!(users[0]["similarity"].as_f64().unwrap() > users[1]["similarity"].as_f64().unwrap());
+}
+
+#[tokio::test]
+async fn test_users_query_rejects_filter_over_depth_limit() {
+    // Test a filter nested past the default depth limit is rejected with its limit named
+    let pool = setup().await;
+    let schema = build_schema(
+        pool,
+        resolvers::Query::default(),
+        resolvers::Mutation::default(),
+    );
+    let mut filter = "{ id: { equals: 1 } }".to_string();
+    for _ in 0..10 {
+        filter = format!("{{ not: {} }}", filter);
+    }
+    let query = format!(
+        "query {{ users(filters: {}) {{ data {{ databaseId }} totalCount }} }}",
+        filter
+    );
+    let result = schema.execute(query.as_str()).await;
+    assert_eq!(result.errors.len(), 1);
+    let extensions = result.errors[0].extensions.as_ref().unwrap();
+    assert_eq!(
+        extensions.get("code"),
+        Some(&async_graphql::Value::from("VALIDATION_FAILED"))
+    );
+    assert_eq!(
+        extensions.get("fields"),
+        Some(&async_graphql::value!([{
+            "field": "filters",
+            "message": "Filter nesting exceeds the maximum depth of 10",
+            "limit": "maxDepth",
+            "max": 10,
+        }]))
+    );
+}
+
+#[tokio::test]
+async fn test_users_query_with_age_between() {
+    // Test between includes both ends of the range
+    let pool = setup().await;
+    seed_test_data(&pool)
+        .await
+        .expect("Failed to seed test data");
+    let schema = build_schema(
+        pool,
+        resolvers::Query::default(),
+        resolvers::Mutation::default(),
+    );
+    let query = r#"
+        query {
+            users(filters: { age: { between: { from: 25, to: 30 } } }) {
+                data {
+                    databaseId
+                }
+                totalCount
+            }
+        }
+    "#;
+    let result = schema.execute(query).await;
+    assert!(result.errors.is_empty());
+    let data = result.data.into_json().unwrap();
+    let ids: Vec<i64> = data["users"]["data"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|u| u["databaseId"].as_i64().unwrap())
+        .collect();
+    assert_eq!(ids, vec![1, 2]);
+}
+
+#[tokio::test]
+async fn test_bind_dynamic_params_keeps_numeric_types() {
+    // Test BIGINT and DOUBLE PRECISION parameters reach Postgres without narrowing
+    let pool = setup().await;
+    let big = 5_000_000_000_i64;
+    let ratio = 0.123456789012345_f64;
+    let params = vec![utils::SqlParam::from(big), utils::SqlParam::from(ratio)];
+    let query = sqlx::query_as::<_, (i64, f64)>("SELECT $1::BIGINT, $2::DOUBLE PRECISION");
+    let row = utils::bind_dynamic_params(query, params)
+        .fetch_one(&pool)
+        .await
+        .expect("Failed to bind numeric params");
+    assert_eq!(row, (big, ratio));
+}
@@ -1,5 +1,6 @@
 import { describe, it, expect } from 'vitest';
 import { buildAgeCondition, buildPostFilters, buildUserFilters, isAgeFilterComplete } from '../../utils/filterBuilders';
+import { NAME_SIMILARITY_THRESHOLD } from '../../constants/constants';
 
 describe('filterBuilders', () => {
   it('buildAgeCondition maps operators correctly', () => {
@@ -21,6 +22,7 @@ describe('filterBuilders', () => {
       or: [
         { title: { containsInsensitive: 'hello' } },
         { content: { containsInsensitive: 'hello' } },
+        { content: { search: 'hello' } },
       ],
     });
   });
@@ -30,6 +32,7 @@ describe('filterBuilders', () => {
       or: [
         { title: { containsInsensitive: 'news' } },
         { content: { containsInsensitive: 'news' } },
+        { content: { search: 'news' } },
       ],
     });
   });
@@ -44,6 +47,7 @@ describe('filterBuilders', () => {
             { name: { containsInsensitive: 'john' } },
             { email: { containsInsensitive: 'john' } },
             { phone: { containsInsensitive: 'john' } },
+            { name: { similarTo: { value: 'john', threshold: NAME_SIMILARITY_THRESHOLD } } },
           ],
         },
       ],
@@ -60,6 +64,7 @@ describe('filterBuilders', () => {
         { name: { containsInsensitive: 'abc' } },
         { email: { containsInsensitive: 'abc' } },
         { phone: { containsInsensitive: 'abc' } },
+        { name: { similarTo: { value: 'abc', threshold: NAME_SIMILARITY_THRESHOLD } } },
       ],
     });
   });
@@ -75,6 +80,7 @@ describe('filterBuilders', () => {
         { name: { containsInsensitive: 'john' } },
         { email: { containsInsensitive: 'john' } },
         { phone: { containsInsensitive: 'john' } },
+        { name: { similarTo: { value: 'john', threshold: NAME_SIMILARITY_THRESHOLD } } },
       ],
     });
   });
@@ -90,6 +96,7 @@ describe('filterBuilders', () => {
             { name: { containsInsensitive: 'john' } },
             { email: { containsInsensitive: 'john' } },
             { phone: { containsInsensitive: 'john' } },
+            { name: { similarTo: { value: 'john', threshold: NAME_SIMILARITY_THRESHOLD } } },
           ],
         },
       ],
@@ -103,6 +110,7 @@ describe('filterBuilders', () => {
         { name: { containsInsensitive: 'john' } },
         { email: { containsInsensitive: 'john' } },
         { phone: { containsInsensitive: 'john' } },
+        { name: { similarTo: { value: 'john', threshold: NAME_SIMILARITY_THRESHOLD } } },
       ],
     });
   });
@@ -164,6 +172,7 @@ describe('filterBuilders', () => {
                 { name: { containsInsensitive: 'john' } },
                 { email: { containsInsensitive: 'john' } },
                 { phone: { containsInsensitive: 'john' } },
+                { name: { similarTo: { value: 'john', threshold: NAME_SIMILARITY_THRESHOLD } } },
               ],
             },
           ],
@@ -180,6 +189,7 @@ describe('filterBuilders', () => {
                 { name: { containsInsensitive: 'john' } },
                 { email: { containsInsensitive: 'john' } },
                 { phone: { containsInsensitive: 'john' } },
+                { name: { similarTo: { value: 'john', threshold: NAME_SIMILARITY_THRESHOLD } } },
               ],
             },
           ],
@@ -0,0 +1,44 @@
+import { describe, it, expect } from 'vitest';
+import { CombinedGraphQLErrors } from '@apollo/client';
+import { errorCode, errorToastMessage } from '../../utils/graphqlErrors';
+
+// A failed response carrying one GraphQL error with the given code
+function graphqlError(message: string, code: string): CombinedGraphQLErrors {
+  return new CombinedGraphQLErrors({
+    errors: [{ message, extensions: { code } }],
+  });
+}
+
+describe('errorCode', () => {
+  //  Should read the code from the first error's extensions
+  it('returns the extensions code', () => {
+    expect(errorCode(graphqlError('Post 9 not found', 'NOT_FOUND'))).toBe('NOT_FOUND');
+  });
+
+  //  Should ignore errors that are not GraphQL errors
+  it('returns undefined for network errors', () => {
+    expect(errorCode(new Error('Failed to fetch'))).toBeUndefined();
+  });
+});
+
+describe('errorToastMessage', () => {
+  //  Should surface messages the user can act on
+  it('uses the server message for validation and conflicts', () => {
+    expect(
+      errorToastMessage(graphqlError('Title cannot be empty', 'VALIDATION_FAILED'), 'Failed')
+    ).toBe('Title cannot be empty');
+    expect(
+      errorToastMessage(graphqlError('Email is already in use: a@b.co', 'CONFLICT'), 'Failed')
+    ).toBe('Email is already in use: a@b.co');
+  });
+
+  //  Should never show internal or unknown failures verbatim
+  it('falls back for internal and network errors', () => {
+    expect(
+      errorToastMessage(graphqlError('Internal server error', 'INTERNAL'), 'Failed to save post')
+    ).toBe('Failed to save post');
+    expect(errorToastMessage(new Error('Failed to fetch'), 'Failed to save post')).toBe(
+      'Failed to save post'
+    );
+  });
+});
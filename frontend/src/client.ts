@@ -1,7 +1,18 @@
 import { ApolloClient, HttpLink } from "@apollo/client";
 import { InMemoryCache } from "@apollo/client";
+import { relayStylePagination } from "@apollo/client/utilities";
 
 export const client = new ApolloClient({
     link: new HttpLink({ uri: "http://localhost:8000/graphql" }),
-    cache: new InMemoryCache(),
-});
\ No newline at end of file
+    cache: new InMemoryCache({
+        typePolicies: {
+            Query: {
+                fields: {
+                    // Merge cursor pages per filter/order combination
+                    usersConnection: relayStylePagination(["filters", "orderBy"]),
+                    postsConnection: relayStylePagination(["filters", "orderBy"]),
+                },
+            },
+        },
+    }),
+});
@@ -1,6 +1,6 @@
 // DataTablePage component that manages Users and Posts tables with tabs, search, and filters.
 
-import { useState, useCallback } from "react";
+import { useState, useCallback, useEffect } from "react";
 import type { SortingState } from "@tanstack/react-table";
 
 import { PostsTable } from "./posts";
@@ -36,6 +36,20 @@ export const DataTablePage = () => {
     resetFilters,
   } = useDataTableFilters(activeTab);
 
+  // Keep the server's relevance order while a search is active,
+  // and go back to the default order once it is cleared
+  const isSearchingUsers = Boolean(
+    userFilters.or?.length || userFilters.and?.some((f) => f.or?.length)
+  );
+  useEffect(() => {
+    setUsersSorting(isSearchingUsers ? [] : [{ id: "id", desc: false }]);
+  }, [isSearchingUsers]);
+
+  const isSearchingPosts = Boolean(postFilters.or?.length);
+  useEffect(() => {
+    setPostsSorting(isSearchingPosts ? [] : [{ id: "createdAt", desc: true }]);
+  }, [isSearchingPosts]);
+
   // Reset filters when switching tabs
   const handleTabChange = useCallback(
     (newTab: Tab) => {
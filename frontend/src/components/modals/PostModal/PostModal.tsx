@@ -5,6 +5,7 @@ import { ModalWrapper } from "../ModelWrapper";
 import { PostModalHeader } from "./PostModalHeader";
 import { PostModalDates } from "./PostModalDates";
 import { MAX_TITLE_LENGTH } from "../../../constants/constants";
+import { errorToastMessage } from "../../../utils/graphqlErrors";
 
 /** 
 PostModal component for creating, editing, or viewing a post. 
@@ -88,10 +89,10 @@ export const PostModal: React.FC<PostModalProps> = ({
 
     try {
       await onDelete();
-      toast.success("Post deleted");
+      toast.success("Post moved to trash");
       onClose();
-    } catch {
-      toast.error("Failed to delete post");
+    } catch (err) {
+      toast.error(errorToastMessage(err, "Failed to delete post"));
     }
   };
 
@@ -119,8 +120,8 @@ export const PostModal: React.FC<PostModalProps> = ({
     try {
       await onSave(trimmedTitle, trimmedContent);
       toast.success(mode === "create" ? "Post created" : "Post updated");
-    } catch {
-      toast.error("Failed to save post");
+    } catch (err) {
+      toast.error(errorToastMessage(err, "Failed to save post"));
     }
   };
 
@@ -104,7 +104,8 @@ export const PostsTableContent = memo(
     // ----- Columns -----
     const columns = useMemo(
       () => [
-        columnHelper.accessor("id", {
+        columnHelper.accessor("databaseId", {
+          id: "id",
           header: "ID",
           cell: (info) => (
             <GenericCell value={info.getValue()} type="number" />
@@ -20,22 +20,23 @@ import { PostModal } from "../../modals/PostModal";
 Type extraction for Post from GetUsersQuery:
 - GetUsersQuery["users"] → UsersConnection object (not an array!)
 - UsersConnection["data"] → User[] array
-- User["posts"] → Post[] array
-- Extract Post type from the posts array
+- User["posts"] → PostsConnection object (a preview page)
+- PostsConnection["data"] → Post[] array
 
-The correct path: users.data[0].posts[0]
+The correct path: users.data[0].posts.data[0]
 We use [number] instead of [0] to get the array element type generically.
 */
 type User = NonNullable<NonNullable<GetUsersQuery["users"]>["data"]>[number];
-type Post = NonNullable<User["posts"]>[number];
+type Post = NonNullable<User["posts"]>["data"][number];
 
 interface PostsHoverCellProps {
   posts: Post[];
+  postsCount: number; // Total posts for the user; `posts` may be a truncated preview
   userName?: string;
   wrapperClassName?: string; // Optional className for the wrapper div
 }
 
-export const PostsHoverCell: React.FC<PostsHoverCellProps> = ({ posts, userName, wrapperClassName }) => {
+export const PostsHoverCell: React.FC<PostsHoverCellProps> = ({ posts, postsCount, userName, wrapperClassName }) => {
   const [isOpen, setIsOpen] = useState(false);
   const [anchorRect, setAnchorRect] = useState<DOMRect | null>(null);
   const [selectedPost, setSelectedPost] = useState<Post | null>(null);
@@ -90,7 +91,7 @@ export const PostsHoverCell: React.FC<PostsHoverCellProps> = ({ posts, userName,
     };
   }, [isOpen, closePopover]);
 
-  if (!posts?.length) {
+  if (!postsCount) {
     return (
       <div className="inline-flex items-center justify-center w-8 h-7 rounded-md text-sm font-semibold select-none bg-gray-100 text-gray-400 border border-gray-200">
         0
@@ -164,7 +165,7 @@ export const PostsHoverCell: React.FC<PostsHoverCellProps> = ({ posts, userName,
       await updatePost({
         variables: {
           input: {
-            id: selectedPost.id,
+            id: selectedPost.databaseId,
             title,
             content,
           },
@@ -181,7 +182,7 @@ export const PostsHoverCell: React.FC<PostsHoverCellProps> = ({ posts, userName,
   const handleDelete = async () => {
     if (!selectedPost) return;
     try {
-      await deletePost({ variables: { id: selectedPost.id } });
+      await deletePost({ variables: { id: selectedPost.databaseId } });
       setSelectedPost(null);
     } catch (err) {
       console.error("Error deleting post:", err);
@@ -296,7 +297,7 @@ export const PostsHoverCell: React.FC<PostsHoverCellProps> = ({ posts, userName,
           >
             <div className="flex items-center justify-between mb-2">
               <h4 className="font-semibold text-sm text-gray-700">
-                {posts.length} {posts.length === 1 ? "Post" : "Posts"}
+                {postsCount} {postsCount === 1 ? "Post" : "Posts"}
               </h4>
               <button
                 className="inline-flex items-center justify-center rounded-md px-2 py-1 text-xs text-gray-500 hover:bg-gray-100"
@@ -358,7 +359,7 @@ export const PostsHoverCell: React.FC<PostsHoverCellProps> = ({ posts, userName,
         onMouseLeave={() => scheduleClose(100)}
         onClick={togglePopover}
       >
-        {posts.length}
+        {postsCount}
       </div>
 
       {popover}
@@ -368,7 +369,7 @@ export const PostsHoverCell: React.FC<PostsHoverCellProps> = ({ posts, userName,
         isOpen={!!selectedPost}
         userName={userName}
         post={selectedPost ? {
-          id: selectedPost.id,
+          id: selectedPost.databaseId,
           title: selectedPost.title,
           content: selectedPost.content,
           createdAt: selectedPost.createdAt,
@@ -120,12 +120,13 @@ export const UsersTableContent = memo(
     }, [hasNextPage, onHasNextPageChange]);
 
     // Active user name for modal header
-    const activeUserName = users.find((u) => u.id === activeUserId)?.name ?? "User";
+    const activeUserName = users.find((u) => u.databaseId === activeUserId)?.name ?? "User";
 
     // ----- Table Columns -----
     const columns = useMemo(
       () => [
-        columnHelper.accessor("id", {
+        columnHelper.accessor("databaseId", {
+          id: "id",
           header: "ID",
           cell: (info) => (
             <GenericCell value={info.getValue()} type="number" />
@@ -166,10 +167,11 @@ export const UsersTableContent = memo(
                 {/* Tooltip showing post previews */}
 
                 <PostsHoverCell
-                  posts={info.getValue() || []}
+                  posts={info.getValue()?.data ?? []}
+                  postsCount={user.postsCount}
                   userName={user.name ?? undefined}
                   wrapperClassName={`inline-flex items-center justify-center w-8 h-7 rounded-md text-sm font-semibold select-none
-    ${info.getValue()?.length > 0
+    ${user.postsCount > 0
                       ? "bg-blue-50 text-blue-700 border border-blue-100 hover:bg-blue-100"
                       : "bg-gray-100 text-gray-400 border border-gray-200"
                     }`}
@@ -177,7 +179,7 @@ export const UsersTableContent = memo(
 
                 {/* Add Post Button */}
                 <button
-                  onClick={() => openModal(user.id)}
+                  onClick={() => openModal(user.databaseId)}
                   className="p-1 text-gray-500 hover:text-blue-600 transition"
                   title="Add Post"
                 >
@@ -240,6 +242,8 @@ export const UsersTableContent = memo(
               closeModal();
             } catch (err) {
               console.error("Error creating post:", err);
+              // Error toast is shown by PostModal
+              throw err;
             }
           }}
         />
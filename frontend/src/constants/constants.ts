@@ -6,6 +6,14 @@ const MAX_TITLE_LENGTH = 50;
 const POSTS_PER_PAGE = 10;
 // Email validation regex
 const EMAIL_REGEX = /^[^\s@]+@[^\s@]+\.[^\s@]+$/;
+// Minimum trigram similarity for typo-tolerant name search (e.g. "Smyth" -> "Smith")
+const NAME_SIMILARITY_THRESHOLD = 0.2;
 
 
-export { USERS_PER_PAGE, MAX_TITLE_LENGTH, POSTS_PER_PAGE, EMAIL_REGEX };
+export {
+  USERS_PER_PAGE,
+  MAX_TITLE_LENGTH,
+  POSTS_PER_PAGE,
+  EMAIL_REGEX,
+  NAME_SIMILARITY_THRESHOLD,
+};
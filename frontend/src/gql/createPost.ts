@@ -5,6 +5,7 @@ export const CREATE_POST = gql`
   mutation CreatePost($input: CreatePostInput!) {
     createPost(input: $input) {
       id
+      databaseId
       title
       content
       createdAt
@@ -9,6 +9,7 @@ export const GET_POSTS = gql`
         posts(filters: $filters, limit: $limit, offset: $offset) {
             data {
                 id
+                databaseId
                 title
                 content
                 createdAt
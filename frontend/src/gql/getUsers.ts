@@ -1,6 +1,6 @@
 /*
 GraphQL query to fetch users with filters/pagination 
-and their posts, including total count of users
+their post count and a preview page of posts, including total count of users
 */
 import { gql } from "@apollo/client";
 
@@ -9,16 +9,21 @@ query GetUsers($filters: UserFilters, $limit: Int, $offset: Int) {
   users(filters: $filters, limit: $limit, offset: $offset) {
     data {
       id
+      databaseId
       name
       age
       email
       phone
-      posts {
-        id
-        title
-        content
-        createdAt
-        updatedAt
+      postsCount
+      posts(limit: 20) {
+        data {
+          id
+          databaseId
+          title
+          content
+          createdAt
+          updatedAt
+        }
       }
     }
     totalCount
@@ -8,6 +8,7 @@ export const UPDATE_POST = gql`
   mutation UpdatePost($input: UpdatePostInput!) {
     updatePost(input: $input) {
       id
+      databaseId
       title
       content
       updatedAt
@@ -4,6 +4,7 @@
 
 import type { UserFilters, PostFilters, IntFilter } from "../__generated__/graphql";
 import type { AgeOperator } from "../types/filters";
+import { NAME_SIMILARITY_THRESHOLD } from "../constants/constants";
 
 /**
  * Builds an IntFilter condition based on the selected operator and value
@@ -30,7 +31,8 @@ export function buildAgeCondition(
 
 /**
  * Builds PostFilters from search query
- * Searches in both title and content fields
+ * Searches in both title and content fields; the full-text `search` on content
+ * makes the backend rank results by relevance
  */
 export function buildPostFilters(searchQuery: string): PostFilters {
   const trimmedQuery = searchQuery.trim();
@@ -43,6 +45,7 @@ export function buildPostFilters(searchQuery: string): PostFilters {
     or: [
       { title: { containsInsensitive: trimmedQuery } },
       { content: { containsInsensitive: trimmedQuery } },
+      { content: { search: trimmedQuery } },
     ],
   };
 }
@@ -57,13 +60,22 @@ export function buildUserFilters(
 ): UserFilters {
   const trimmedQuery = searchQuery.trim();
   
-  // Build search filter for name, email, or phone
+  // Build search filter for name, email, or phone, plus a typo-tolerant
+  // name match that the backend also uses to order results by similarity
   const searchFilter: UserFilters | null = trimmedQuery
     ? {
         or: [
           { name: { containsInsensitive: trimmedQuery } },
           { email: { containsInsensitive: trimmedQuery } },
           { phone: { containsInsensitive: trimmedQuery } },
+          {
+            name: {
+              similarTo: {
+                value: trimmedQuery,
+                threshold: NAME_SIMILARITY_THRESHOLD,
+              },
+            },
+          },
         ],
       }
     : null;
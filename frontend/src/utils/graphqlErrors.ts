@@ -0,0 +1,29 @@
+import { CombinedGraphQLErrors } from "@apollo/client";
+
+// Error codes the backend sets in `extensions.code`
+export type ApiErrorCode =
+  | "NOT_FOUND"
+  | "VALIDATION_FAILED"
+  | "CONFLICT"
+  | "FOREIGN_KEY"
+  | "INTERNAL";
+
+// Code of the first GraphQL error, if the request failed with one
+export const errorCode = (error: unknown): ApiErrorCode | undefined => {
+  if (!CombinedGraphQLErrors.is(error)) return undefined;
+  return error.errors[0]?.extensions?.code as ApiErrorCode | undefined;
+};
+
+// Message to show for a failed request: the server's own message for errors
+// the user can act on, `fallback` for internal or network failures
+export const errorToastMessage = (error: unknown, fallback: string): string => {
+  switch (errorCode(error)) {
+    case "NOT_FOUND":
+    case "VALIDATION_FAILED":
+    case "CONFLICT":
+    case "FOREIGN_KEY":
+      return (error as CombinedGraphQLErrors).errors[0].message;
+    default:
+      return fallback;
+  }
+};